@@ -1,94 +1,548 @@
 use crate::player::CliPlayer;
+use crate::recall;
 use crate::utils::{clear_screen, press_any_to_continue};
-use video_poker_core::{bonus_game, score_board, video_poker};
+use std::fs;
+use std::io;
+use video_poker_core::audit::{self, AuditLog};
+use video_poker_core::bankroll;
+use video_poker_core::bonus_game::BonusRound;
+use video_poker_core::bonus_game::{self, Gamble};
+use video_poker_core::{Card, Money, spin_poker};
+use video_poker_core::{progressive, recall::Recall, recall::Record, score_board, selftest};
+use video_poker_core::{strategy, ultimate_x, video_poker};
 
 use dialoguer::Select;
 
 pub struct CliGame {
     game: video_poker::VideoPoker,
+    spin_poker: spin_poker::SpinPoker,
     bonus_game: bonus_game::BonusGame,
     player: CliPlayer,
     score_board: score_board::ScoreBoard,
+    recall: Recall,
+    audit: Option<AuditLog>,
+}
+
+enum Mode {
+    VideoPoker,
+    UltimateX,
+    SpinPoker,
+    Recall,
 }
 
 impl CliGame {
+    pub const AUDIT_LOG: &str = "audit.log";
+    const PROGRESSIVE: &str = "progressive.meter";
+    const RULE: video_poker::Rule = video_poker::Rule::Jokers54Cards;
+
+    pub fn verify(&mut self, path: &str) {
+        match fs::read_to_string(path) {
+            Ok(log) => {
+                match audit::verify(
+                    &log,
+                    &mut self.game,
+                    &mut self.spin_poker,
+                    &self.score_board,
+                    &CliGame::GAMBLES,
+                ) {
+                    Ok(records) => println!("{} records verified.", records),
+                    Err(e) => println!("{}", e),
+                }
+            }
+            Err(e) => println!("Failed to read {}: {}", path, e),
+        }
+    }
+
+    pub fn selftest(trials: usize) {
+        let seed = rand::random();
+        println!("Seed: {}", seed);
+        let mut passed = true;
+        for (name, rule) in [
+            ("52 cards", video_poker::Rule::Default52Cards),
+            ("54 cards", video_poker::Rule::Jokers54Cards),
+        ] {
+            println!("{}", name);
+            for check in selftest::run(rule, trials, seed) {
+                println!(
+                    "  {:<36} chi-square {:>10.2} df {:>5} p {:.4} {}",
+                    check.name,
+                    check.chi_square,
+                    check.degrees,
+                    check.p_value,
+                    if check.passed() { "PASS" } else { "FAIL" }
+                );
+                passed &= check.passed();
+            }
+        }
+        println!(
+            "{}",
+            if passed {
+                "All checks passed."
+            } else {
+                "Some checks failed."
+            }
+        );
+    }
+
+    pub fn compare(baseline: &str, other: &str, deals: usize) {
+        let (Some(baseline_player), Some(other_player)) =
+            (CliGame::strategy(baseline), CliGame::strategy(other))
+        else {
+            println!("Strategies are: random, naive, chart, perfect");
+            return;
+        };
+        let seed = rand::random();
+        println!("Seed: {}", seed);
+        let comparison = strategy::compare(
+            CliGame::RULE,
+            &score_board::Rate::JOKER_POKER,
+            baseline_player.as_ref(),
+            other_player.as_ref(),
+            deals,
+            seed,
+        );
+        for (name, total) in [(baseline, &comparison.baseline), (other, &comparison.other)] {
+            println!(
+                "{:<8} paid {:>7.2}% expected {:>7.2}%",
+                name,
+                total.paid * 100.0,
+                total.expected * 100.0
+            );
+        }
+        println!(
+            "Difference: paid {:.2}% expected {:.2}%",
+            comparison.rtp_difference() * 100.0,
+            comparison.ev_difference() * 100.0
+        );
+        println!(
+            "{:<24} {:>9} {:>9} {:>9} {:>9}",
+            "Situation", "Frequency", "Mistakes", "Per miss", "Cost"
+        );
+        for loss in &comparison.situations {
+            println!(
+                "{:<24} {:>8.2}% {:>9} {:>9.3} {:>8.3}%",
+                loss.situation,
+                loss.deals as f64 * 100.0 / deals.max(1) as f64,
+                loss.mistakes,
+                loss.ev_lost / loss.mistakes.max(1) as f64,
+                loss.cost(deals) * 100.0
+            );
+        }
+    }
+
+    pub fn risk(bankroll: usize, bet: usize, rounds: usize, strategy: &str) {
+        if bet < CliGame::COSTS[0] {
+            println!("The minimum bet is {} tokens.", CliGame::COSTS[0]);
+            return;
+        }
+        let Some(player) = CliGame::strategy(strategy) else {
+            println!("Strategies are: random, naive, chart, perfect");
+            return;
+        };
+        let seed = rand::random();
+        println!("Seed: {}", seed);
+        let distribution = bankroll::distribution(CliGame::RULE, player.as_ref(), 100_000, seed);
+        println!(
+            "Return to player with {} play: {:.2}%",
+            strategy,
+            bankroll::return_to_player(&score_board::Rate::JOKER_POKER, &distribution) * 100.0
+        );
+        let session = bankroll::Session::new(bankroll, bet, rounds).with_minimum(CliGame::COSTS[0]);
+        let risk = bankroll::risk(
+            &score_board::Rate::JOKER_POKER,
+            &distribution,
+            &session,
+            10_000,
+            seed,
+        );
+        println!(
+            "Bankroll {} betting {} for {} rounds over {} sessions",
+            bankroll, bet, rounds, risk.sessions
+        );
+        println!("Probability of going broke: {:.2}%", risk.ruin * 100.0);
+        match risk.expected_rounds {
+            Some(expected) => println!(
+                "Expected rounds until below {} tokens: {:.1}",
+                CliGame::COSTS[0],
+                expected
+            ),
+            None => println!(
+                "Expected rounds until below {} tokens: unbounded",
+                CliGame::COSTS[0]
+            ),
+        }
+        println!("Mean ending bankroll: {:.0}", risk.mean_ending());
+        for percent in [5.0, 25.0, 50.0, 75.0, 95.0] {
+            println!(
+                "  {:>3}th percentile: {}",
+                percent,
+                risk.percentile(percent)
+            );
+        }
+    }
+
+    fn strategy(name: &str) -> Option<Box<dyn video_poker::Player>> {
+        match name {
+            "random" => Some(Box::new(strategy::RandomPlayer::new(rand::random()))),
+            "naive" => Some(Box::new(strategy::NaivePlayer)),
+            "chart" => Some(Box::new(strategy::ChartPlayer)),
+            "perfect" => Some(Box::new(strategy::PerfectPlayer::new(
+                CliGame::RULE,
+                score_board::Rate::JOKER_POKER,
+            ))),
+            _ => None,
+        }
+    }
+
     pub fn start(&mut self) {
-        while self.next_round() {
+        self.load_progressive();
+        self.open_audit();
+        while let Some(mode) = self.next_round() {
             clear_screen();
+            let token_before = self.score_board.token();
+            let played = match mode {
+                Mode::VideoPoker => self.video_poker_round(),
+                Mode::UltimateX => self.ultimate_x_round(),
+                Mode::SpinPoker => self.spin_poker_round(),
+                Mode::Recall => {
+                    recall::show(&self.recall);
+                    None
+                }
+            };
+            if let Some((bet, bonus)) = played {
+                let round = match mode {
+                    Mode::SpinPoker => self.spin_poker.round(),
+                    _ => self.game.round(),
+                };
+                self.recall.push(Record {
+                    bet,
+                    token_before,
+                    token_after: self.score_board.token(),
+                    round: round.clone(),
+                    bonus,
+                });
+                self.save_progressive();
+            }
+        }
+    }
 
-            let mut cost: usize;
-            loop {
-                clear_screen();
-                self.show_token();
-                cost = self.choose_cost();
-                if self.score_board.cost(cost).is_ok() {
-                    break;
-                } else {
-                    println!("You don't have enough tokens.");
+    fn open_audit(&mut self) {
+        let credits = self.score_board.token();
+        match AuditLog::open(CliGame::AUDIT_LOG)
+            .and_then(|mut log| log.open_session(credits).map(|_| log))
+        {
+            Ok(log) => self.audit = Some(log),
+            Err(e) => {
+                println!(
+                    "Failed to open the audit log, rounds won't be audited: {}",
+                    e
+                );
+                press_any_to_continue();
+            }
+        }
+    }
+
+    fn load_progressive(&self) {
+        if let Some(progressive) = self.score_board.progressive() {
+            match progressive.load(CliGame::PROGRESSIVE) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    println!("Failed to load the progressive: {}", e);
                     press_any_to_continue();
                 }
+                _ => {}
             }
+        }
+    }
 
-            let result = self.game.start(&mut self.player);
-            if let Some(hand) = result.as_ref() {
-                println!("The result is: {}", hand);
-                println!("The prize is: {}", self.score_board.prize(cost, hand, None));
+    fn save_progressive(&self) {
+        if let Some(progressive) = self.score_board.progressive()
+            && let Err(e) = progressive.save(CliGame::PROGRESSIVE)
+        {
+            println!("Failed to save the progressive: {}", e);
+            press_any_to_continue();
+        }
+    }
+
+    fn video_poker_round(&mut self) -> Option<(Money, Vec<BonusRound>)> {
+        let credits_before = self.score_board.token();
+        let cost = self.place_bet(1)?;
+
+        let result = self.game.start(&mut self.player, &mut rand::rng());
+        let jackpot = CliGame::apply_progressive(&mut self.score_board, cost, self.game.cards());
+        if let Some(jackpot) = jackpot {
+            println!("JACKPOT! You've won the progressive of {} tokens", jackpot);
+            press_any_to_continue();
+        }
+        let mut entry = audit::Entry {
+            game: audit::Game::VideoPoker,
+            bet: cost,
+            round: self.game.round().clone(),
+            prize: Money::ZERO,
+            jackpot: jackpot.unwrap_or(Money::ZERO),
+            gamble: None,
+            bonus: Vec::new(),
+            credits_before,
+            credits_after: Money::ZERO,
+        };
+        // The progressive replaces the award of the hand, so it is never doubled.
+        if let Some(hand) = result.as_ref().filter(|_| jackpot.is_none()) {
+            self.game
+                .feature_status()
+                .iter()
+                .for_each(|status| println!("{}", status));
+            entry.prize = self
+                .score_board
+                .prize_hands(&mut self.game, cost, &[Some(*hand)]);
+            println!("The result is: {}", hand);
+            println!("The prize is: {}", entry.prize);
+            press_any_to_continue();
+
+            let gamble = self.choose_gamble();
+            let bonus = self.bonus_game.start(gamble, &mut self.player, entry.prize);
+            if bonus.is_lost() {
+                println!("You've lost the game.");
                 press_any_to_continue();
-            } else {
-                println!("You didn't get a hand.");
+            }
+            if !bonus.is_lost() || bonus.banked > Money::ZERO {
+                match self.score_board.apply(bonus) {
+                    Ok(paid) => println!("The final result is {}", paid),
+                    Err(e) => println!("{}", e),
+                }
                 press_any_to_continue();
-                continue;
-            };
+            }
+            entry.gamble = Some(gamble.name().to_string());
+            entry.bonus = self.bonus_game.rounds().to_vec();
+        } else if result.is_none() {
+            println!("You didn't get a hand.");
+            press_any_to_continue();
+        }
+        entry.credits_after = self.score_board.token();
+        self.audit(&entry);
+        Some((cost, entry.bonus))
+    }
 
-            let bonus = self.bonus_game.start(&mut self.player);
-            if let Some(round) = bonus.as_ref() {
-                println!("The bonus is x{}", 2_usize.pow(*round as u32));
-                press_any_to_continue();
-            } else {
-                println!("You've lost the game.");
+    fn apply_progressive(
+        score_board: &mut score_board::ScoreBoard,
+        bet: Money,
+        cards: &[Card],
+    ) -> Option<Money> {
+        score_board
+            .apply_progressive(bet, cards)
+            .unwrap_or_else(|e| {
+                println!("{}", e);
+                None
+            })
+    }
+
+    fn audit(&mut self, entry: &audit::Entry) {
+        if let Some(log) = self.audit.as_mut()
+            && let Err(e) = log.append(entry)
+        {
+            println!("Failed to write the audit log: {}", e);
+            press_any_to_continue();
+        }
+    }
+
+    fn ultimate_x_round(&mut self) -> Option<(Money, Vec<BonusRound>)> {
+        let credits_before = self.score_board.token();
+        let ultimate_x = self.score_board.ultimate_x()?;
+        let (hands, wager) = (
+            ultimate_x.hands(),
+            ultimate_x.wager(Money::new(1))?.amount(),
+        );
+        let multipliers = ultimate_x.multipliers().to_vec();
+        let bet = self.place_bet(wager)?;
+
+        let results = self
+            .game
+            .start_multi(&mut self.player, hands, &mut rand::rng());
+        let (mut jackpot, mut jackpots) = (Money::ZERO, Vec::new());
+        for (i, cards) in self.game.hands().iter().enumerate() {
+            if let Some(won) = CliGame::apply_progressive(&mut self.score_board, bet, cards) {
+                println!(
+                    "JACKPOT! Hand {} won the progressive of {} tokens",
+                    i + 1,
+                    won
+                );
+                jackpot = jackpot.saturating_add(won);
+                jackpots.push(i);
+            }
+        }
+        results
+            .iter()
+            .zip(&multipliers)
+            .enumerate()
+            .for_each(|(i, (result, multiplier))| match result {
+                Some(hand) if jackpots.contains(&i) => {
+                    println!("Hand {}: {} = progressive", i + 1, hand)
+                }
+                Some(hand) => println!(
+                    "Hand {}: {} x{} = {}",
+                    i + 1,
+                    hand,
+                    multiplier,
+                    self.score_board
+                        .prize(bet, hand)
+                        .saturating_mul(*multiplier)
+                ),
+                None => println!("Hand {}: -", i + 1),
+            });
+        self.game
+            .feature_status()
+            .iter()
+            .for_each(|status| println!("{}", status));
+        // Only what was credited goes to the audit log.
+        let prize = match self.score_board.apply_hands(&mut self.game, bet, &results) {
+            Ok(prize) => prize,
+            Err(e) => {
+                println!("{}", e);
+                Money::ZERO
+            }
+        };
+        println!("The prize is: {}", prize);
+        press_any_to_continue();
+        self.audit(&audit::Entry {
+            game: audit::Game::UltimateX(multipliers),
+            bet,
+            round: self.game.round().clone(),
+            prize,
+            jackpot,
+            gamble: None,
+            bonus: Vec::new(),
+            credits_before,
+            credits_after: self.score_board.token(),
+        });
+        Some((bet, Vec::new()))
+    }
+
+    fn spin_poker_round(&mut self) -> Option<(Money, Vec<BonusRound>)> {
+        let credits_before = self.score_board.token();
+        let bet = self.place_bet(self.spin_poker.paylines().len())?;
+
+        let results = self.spin_poker.start(&mut self.player, &mut rand::rng());
+        results.iter().enumerate().for_each(|(i, result)| {
+            if let Some(hand) = result {
+                println!(
+                    "Line {}: {} = {}",
+                    i + 1,
+                    hand,
+                    self.score_board.prize(bet, hand)
+                );
+            }
+        });
+        let prize = self.score_board.prize_lines(bet, &results);
+        match self.score_board.apply_win(prize) {
+            Ok(prize) => println!("The prize is: {}", prize),
+            Err(e) => println!("{}", e),
+        }
+        press_any_to_continue();
+        self.audit(&audit::Entry {
+            game: audit::Game::SpinPoker,
+            bet,
+            round: self.spin_poker.round().clone(),
+            prize,
+            jackpot: Money::ZERO,
+            gamble: None,
+            bonus: Vec::new(),
+            credits_before,
+            credits_after: self.score_board.token(),
+        });
+        Some((bet, Vec::new()))
+    }
+
+    fn place_bet(&mut self, wager: usize) -> Option<Money> {
+        if Money::new(CliGame::COSTS[0])
+            .checked_mul(wager)
+            .is_none_or(|minimum| self.score_board.token() < minimum)
+        {
+            println!("You don't have enough tokens for this game.");
+            press_any_to_continue();
+            return None;
+        }
+        loop {
+            clear_screen();
+            self.show_token();
+            let bet = Money::new(self.choose_cost(wager));
+            let Some(cost) = bet.checked_mul(wager) else {
+                println!("The bet is too large.");
                 press_any_to_continue();
                 continue;
             };
-
-            println!(
-                "The final result is {}",
-                self.score_board
-                    .prize(cost, result.as_ref().unwrap(), bonus)
-            );
-            self.score_board
-                .apply(cost, result.unwrap(), bonus.unwrap());
-            press_any_to_continue();
+            if self.score_board.cost(cost).is_ok() {
+                return Some(bet);
+            } else {
+                println!("You don't have enough tokens.");
+                press_any_to_continue();
+            }
         }
     }
 
-    fn next_round(&self) -> bool {
+    fn next_round(&self) -> Option<Mode> {
         clear_screen();
-        if self.score_board.token() < 50 {
+        if self.score_board.token() < Money::new(CliGame::COSTS[0]) {
             println!("You don't have enough tokens to start a new round.");
             press_any_to_continue();
             println!("Game Over.");
             press_any_to_continue();
-            false
+            None
         } else {
-            Select::new()
+            match Select::new()
                 .with_prompt("Start a new game?")
-                .items(vec!["Start", "Exit"])
+                .items(vec!["Start", "Ultimate X", "Spin Poker", "Recall", "Exit"])
                 .default(0)
                 .interact()
                 .unwrap()
-                == 0
+            {
+                0 => Some(Mode::VideoPoker),
+                1 => Some(Mode::UltimateX),
+                2 => Some(Mode::SpinPoker),
+                3 => Some(Mode::Recall),
+                _ => None,
+            }
         }
     }
 
     fn show_token(&self) {
         println!("You currently have {} tokens", self.score_board.token());
+        if let Some(progressive) = self.score_board.progressive() {
+            println!(
+                "Progressive jackpot: {} tokens (natural {} at {} tokens)",
+                progressive.amount(),
+                progressive.hand(),
+                progressive.bet()
+            );
+        }
+        if let Some(ultimate_x) = self.score_board.ultimate_x() {
+            print!("Ultimate X multipliers:");
+            ultimate_x
+                .multipliers()
+                .iter()
+                .for_each(|multiplier| print!(" x{}", multiplier));
+            println!();
+        }
     }
 
     const COSTS: [usize; 5] = [50, 100, 200, 500, 1000];
-    fn choose_cost(&self) -> usize {
+    fn choose_cost(&self, wager: usize) -> usize {
         CliGame::COSTS[Select::new()
             .with_prompt("Choose the token to spend")
-            .items(CliGame::COSTS)
+            .items(CliGame::COSTS.map(|cost| Money::new(cost).saturating_mul(wager)))
+            .default(0)
+            .interact()
+            .unwrap()]
+    }
+
+    const GAMBLES: [&dyn Gamble; 4] = [
+        &bonus_game::HighLow,
+        &bonus_game::RedBlack,
+        &bonus_game::Suits,
+        &bonus_game::DealerCard,
+    ];
+    fn choose_gamble(&self) -> &'static dyn Gamble {
+        clear_screen();
+        CliGame::GAMBLES[Select::new()
+            .with_prompt("Choose the bonus game")
+            .items(CliGame::GAMBLES.map(|gamble| gamble.name()))
             .default(0)
             .interact()
             .unwrap()]
@@ -97,23 +551,43 @@ impl CliGame {
 
 impl Default for CliGame {
     fn default() -> Self {
-        let rate = score_board::Rate {
-            royal_flush: 500,
-            five_of_a_kind: 200,
-            straight_flush: 120,
-            four_of_a_kind: 50,
-            full_house: 10,
-            flush: 7,
-            straight: 5,
-            three_of_a_kind: 3,
-            two_pair: 2,
-            jacks_or_better: 1,
+        let multipliers = score_board::Rate {
+            royal_flush: 4,
+            five_of_a_kind: 4,
+            straight_flush: 4,
+            four_of_a_kind: 3,
+            full_house: 12,
+            flush: 11,
+            straight: 7,
+            three_of_a_kind: 4,
+            two_pair: 3,
+            jacks_or_better: 2,
         };
+        let progressive = progressive::Progressive::new(
+            Money::new(10_000),
+            1,
+            video_poker::Hand::RoyalFlush,
+            Money::new(CliGame::COSTS[CliGame::COSTS.len() - 1]),
+        );
         Self {
-            game: video_poker::VideoPoker::new(video_poker::Rule::Jokers54Cards),
-            bonus_game: bonus_game::BonusGame::new(),
-            player: CliPlayer::new(),
-            score_board: score_board::ScoreBoard::new(200, rate, 2),
+            game: video_poker::VideoPoker::new(CliGame::RULE)
+                .with_feature(video_poker::SuperTimesPay::new(0.05)),
+            spin_poker: spin_poker::SpinPoker::new(
+                CliGame::RULE,
+                spin_poker::SpinPoker::default_paylines(),
+            )
+            .expect("Default paylines are valid"),
+            bonus_game: bonus_game::BonusGame::new(bonus_game::Dealing::Shoe, &mut rand::rng()),
+            player: CliPlayer::new().with_progressive(progressive.clone()),
+            score_board: score_board::ScoreBoard::new(
+                Money::new(200),
+                score_board::Rate::JOKER_POKER,
+            )
+            .with_win_cap(Money::new(2_000_000))
+            .with_progressive(progressive)
+            .with_ultimate_x(ultimate_x::UltimateX::new(multipliers, 3)),
+            recall: Recall::new(10),
+            audit: None,
         }
     }
 }
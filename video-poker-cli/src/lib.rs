@@ -1,5 +1,6 @@
 mod cli;
 mod player;
+mod recall;
 mod utils;
 
 pub use cli::CliGame;
@@ -1,5 +1,35 @@
 use video_poker_cli::CliGame;
 
 fn main() {
-    CliGame::default().start();
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("verify") => CliGame::default().verify(
+            args.get(2)
+                .map(String::as_str)
+                .unwrap_or(CliGame::AUDIT_LOG),
+        ),
+        Some("selftest") => CliGame::selftest(
+            args.get(2)
+                .and_then(|trials| trials.parse().ok())
+                .unwrap_or(1_000_000),
+        ),
+        Some("compare") => CliGame::compare(
+            args.get(2).map(String::as_str).unwrap_or("perfect"),
+            args.get(3).map(String::as_str).unwrap_or("naive"),
+            args.get(4)
+                .and_then(|deals| deals.parse().ok())
+                .unwrap_or(1_000),
+        ),
+        Some("risk") => CliGame::risk(
+            args.get(2)
+                .and_then(|bankroll| bankroll.parse().ok())
+                .unwrap_or(200),
+            args.get(3).and_then(|bet| bet.parse().ok()).unwrap_or(50),
+            args.get(4)
+                .and_then(|rounds| rounds.parse().ok())
+                .unwrap_or(100),
+            args.get(5).map(String::as_str).unwrap_or("chart"),
+        ),
+        _ => CliGame::default().start(),
+    }
 }
@@ -1,72 +1,163 @@
 use crate::utils::{clear_screen, press_any_to_continue};
-use dialoguer::{MultiSelect, Select};
-use video_poker_core::{Card, bonus_game, video_poker};
+use console::{Key, Term};
+use dialoguer::Select;
+use video_poker_core::{Card, Money, bonus_game, progressive, spin_poker, video_poker};
 
-pub struct CliPlayer {}
+pub struct CliPlayer {
+    progressive: Option<progressive::Progressive>,
+}
 
 impl CliPlayer {
     pub fn new() -> Self {
-        CliPlayer {}
+        CliPlayer { progressive: None }
+    }
+
+    pub fn with_progressive(mut self, progressive: progressive::Progressive) -> Self {
+        self.progressive = Some(progressive);
+        self
     }
 
-    fn print_hand(cards: &[Card]) {
+    pub fn print_hand(cards: &[Card]) {
         cards.iter().for_each(|card| print!("{} ", card));
         println!();
     }
+
+    fn row(items: impl Iterator<Item = String>) -> String {
+        items.map(|item| format!("{:^6}", item)).collect()
+    }
+
+    fn print_holds(term: &Term, held: &[bool], cursor: usize) {
+        let lines = [
+            CliPlayer::row(
+                held.iter()
+                    .map(|held| if *held { "HELD" } else { "" }.to_string()),
+            ),
+            CliPlayer::row((0..held.len()).map(|i| if i == cursor { "^" } else { "" }.to_string())),
+            "Press 1-5 or use arrows and space to hold, enter to draw".to_string(),
+        ];
+        lines
+            .iter()
+            .for_each(|line| term.write_line(line.trim_end()).unwrap());
+    }
 }
 
 impl video_poker::Player for CliPlayer {
     fn show_cards(&self, cards: &[Card]) {
         clear_screen();
-        print!("Your hand is: ");
-        CliPlayer::print_hand(cards);
+        if let Some(progressive) = self.progressive.as_ref() {
+            println!("Progressive jackpot: {}", progressive.amount());
+        }
+        println!("Your hand is:");
+        println!("{}", CliPlayer::row(cards.iter().map(Card::to_string)));
     }
 
     fn exchange(&self, cards: &[Card]) -> Vec<usize> {
-        let selection = MultiSelect::new()
-            .max_length(20)
-            .with_prompt("Please select the cards to keep")
-            .items(cards.iter())
-            .interact()
-            .unwrap();
-        (0..5).filter(|i| !selection.contains(i)).collect()
+        let term = Term::stdout();
+        let mut held = vec![false; cards.len()];
+        let mut cursor = 0;
+        term.hide_cursor().unwrap();
+        loop {
+            CliPlayer::print_holds(&term, &held, cursor);
+            match term.read_key().unwrap() {
+                Key::Char(c @ '1'..='9') if (c as usize - '1' as usize) < cards.len() => {
+                    cursor = c as usize - '1' as usize;
+                    held[cursor] = !held[cursor];
+                }
+                Key::ArrowLeft => cursor = cursor.saturating_sub(1),
+                Key::ArrowRight => cursor = (cursor + 1).min(cards.len() - 1),
+                Key::Char(' ') => held[cursor] = !held[cursor],
+                Key::Enter => break,
+                Key::CtrlC => {
+                    term.show_cursor().unwrap();
+                    std::process::exit(130);
+                }
+                _ => {}
+            }
+            term.clear_last_lines(3).unwrap();
+        }
+        term.show_cursor().unwrap();
+        (0..cards.len()).filter(|i| !held[*i]).collect()
+    }
+
+    fn show_hands(&self, hands: &[Vec<Card>]) {
+        clear_screen();
+        hands.iter().enumerate().for_each(|(i, cards)| {
+            print!("Hand {}: ", i + 1);
+            CliPlayer::print_hand(cards);
+        });
+    }
+}
+
+impl spin_poker::Player for CliPlayer {
+    fn show_grid(&self, grid: &[Vec<Card>]) {
+        clear_screen();
+        grid.iter().for_each(|row| {
+            row.iter()
+                .for_each(|card| print!("{:<4}", card.to_string()));
+            println!();
+        });
+        println!();
     }
 }
 
 impl bonus_game::Player for CliPlayer {
-    fn new_round(&self, current_round: usize) -> bool {
+    fn new_round(&self, bonus: &bonus_game::Bonus, history: &[Card]) -> bonus_game::Choice {
         clear_screen();
-        println!("Current bonus is x{}", 2_usize.pow(current_round as u32));
-        Select::new()
+        if !history.is_empty() {
+            print!("Cards drawn from the shoe: ");
+            CliPlayer::print_hand(history);
+        }
+        println!("Current bonus is {}", bonus.at_risk);
+        if bonus.banked > Money::ZERO {
+            println!("Banked: {}", bonus.banked);
+        }
+        match Select::new()
             .with_prompt("Start a Bonus game?")
-            .items(vec!["Yes", "No"])
+            .items(vec!["Double", "Double half", "Collect"])
             .default(0)
             .interact()
             .unwrap()
-            == 0
+        {
+            0 => bonus_game::Choice::Double,
+            1 => bonus_game::Choice::DoubleHalf,
+            _ => bonus_game::Choice::Collect,
+        }
     }
 
-    fn guess(&self) -> bonus_game::Guess {
+    fn guess(&self, face_up: &[Card], hints: &[bonus_game::Hint]) -> bonus_game::Guess {
         clear_screen();
+        if !face_up.is_empty() {
+            print!("The dealer's card is: ");
+            CliPlayer::print_hand(face_up);
+        }
         let selection = Select::new()
-            .with_prompt("The card is greater or less than 7?")
-            .items(vec!["Greater", "Less"])
+            .with_prompt("Make your guess")
+            .items(hints.iter().map(|hint| {
+                format!(
+                    "{} (win {:.1}%, EV {:.2})",
+                    hint.guess,
+                    hint.win * 100.0,
+                    hint.ev
+                )
+            }))
             .default(0)
             .interact()
             .unwrap();
-        if selection == 0 {
-            bonus_game::Guess::Greater
-        } else {
-            bonus_game::Guess::Less
-        }
+        hints[selection].guess.clone()
     }
 
-    fn round_result(&self, card: &Card, _guess: &bonus_game::Guess, win: bool) {
-        println!("The card is {card}");
-        if win {
-            println!("You've won the round");
-        } else {
-            println!("You've lost");
+    fn round_result(
+        &self,
+        cards: &[Card],
+        _guess: &bonus_game::Guess,
+        outcome: &bonus_game::Outcome,
+    ) {
+        print!("The cards are: ");
+        CliPlayer::print_hand(cards);
+        match outcome {
+            bonus_game::Outcome::Win => println!("You've won the round"),
+            bonus_game::Outcome::Lose => println!("You've lost"),
+            bonus_game::Outcome::Push => println!("It's a push"),
         }
         press_any_to_continue();
     }
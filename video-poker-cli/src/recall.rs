@@ -0,0 +1,58 @@
+use crate::player::CliPlayer;
+use crate::utils::{clear_screen, press_any_to_continue};
+use dialoguer::Select;
+use video_poker_core::recall::{Recall, Record};
+
+pub fn show(recall: &Recall) {
+    if recall.is_empty() {
+        println!("There is no game to recall.");
+        press_any_to_continue();
+        return;
+    }
+    let mut games_ago = 0;
+    loop {
+        clear_screen();
+        println!("Game {} of {} (latest first)", games_ago + 1, recall.len());
+        print_record(recall.get(games_ago).unwrap());
+        match Select::new()
+            .items(vec!["Previous game", "Next game", "Back"])
+            .default(0)
+            .interact()
+            .unwrap()
+        {
+            0 => games_ago = (games_ago + 1).min(recall.len() - 1),
+            1 => games_ago = games_ago.saturating_sub(1),
+            _ => return,
+        }
+    }
+}
+
+fn print_record(record: &Record) {
+    println!(
+        "Tokens: {} -> {} (bet {})",
+        record.token_before, record.token_after, record.bet
+    );
+    print!("Dealt: ");
+    CliPlayer::print_hand(&record.round.dealt);
+    print!("Held:");
+    record
+        .round
+        .held
+        .iter()
+        .for_each(|i| print!(" {}", record.round.dealt[*i]));
+    println!();
+    record.round.hands.iter().for_each(|cards| {
+        print!("Drawn: ");
+        CliPlayer::print_hand(cards);
+    });
+    record.bonus.iter().enumerate().for_each(|(i, round)| {
+        print!(
+            "Bonus {}: {}, guess {}, {} with ",
+            i + 1,
+            round.choice,
+            round.guess,
+            round.outcome
+        );
+        CliPlayer::print_hand(&round.cards);
+    });
+}
@@ -0,0 +1,35 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use video_poker_core::video_poker::Hand;
+use video_poker_core::{Card, Rank, Suit};
+
+const SUITS: [Suit; 5] = [Suit::Heart, Suit::Spade, Suit::Diamond, Suit::Club, Suit::Joker];
+const RANKS: [Rank; 13] = [
+    Rank::Ace,
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+];
+
+fuzz_target!(|data: &[u8]| {
+    let mut cards: Vec<Card> = data
+        .iter()
+        .map(|byte| Card {
+            suit: SUITS[*byte as usize / RANKS.len() % SUITS.len()].clone(),
+            rank: RANKS[*byte as usize % RANKS.len()].clone(),
+        })
+        .collect();
+    let hand = Hand::from_cards(&cards);
+    cards.reverse();
+    assert_eq!(hand, Hand::from_cards(&cards));
+});
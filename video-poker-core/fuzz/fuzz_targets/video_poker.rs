@@ -0,0 +1,35 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use video_poker_core::Card;
+use video_poker_core::video_poker::{Player, Rule, VideoPoker};
+
+struct Exchange(Vec<usize>);
+
+impl Player for Exchange {
+    fn show_cards(&self, _cards: &[Card]) {}
+
+    fn exchange(&self, _cards: &[Card]) -> Vec<usize> {
+        self.0.clone()
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((seed, rest)) = data.split_first_chunk::<8>() else {
+        return;
+    };
+    let Some((hands, indices)) = rest.split_first() else {
+        return;
+    };
+    let hands = *hands as usize % 5 + 1;
+    let mut player = Exchange(indices.iter().map(|i| *i as usize).collect());
+    let mut game = VideoPoker::new(Rule::Jokers54Cards);
+    game.start_seeded(&mut player, hands, u64::from_le_bytes(*seed));
+
+    let round = game.round();
+    assert_eq!(round.hands.len(), hands);
+    for cards in &round.hands {
+        assert_eq!(cards.len(), 5);
+        assert!(round.held.iter().all(|i| cards[*i] == round.dealt[*i]));
+    }
+});
@@ -0,0 +1,660 @@
+use crate::bonus_game::{BonusGame, BonusRound, Gamble};
+use crate::card::Card;
+use crate::money::Money;
+use crate::score_board::ScoreBoard;
+use crate::spin_poker::{self, SpinPoker};
+use crate::ultimate_x::UltimateX;
+use crate::video_poker::{self, Round, VideoPoker};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use sha2::{Digest, Sha256};
+#[cfg(feature = "std")]
+use std::fs::{self, File, OpenOptions};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const SESSION: &str = "Session ";
+
+#[derive(Clone, PartialEq)]
+pub enum Game {
+    VideoPoker,
+    // The Ultimate X multiplier of every hand in the round.
+    UltimateX(Vec<usize>),
+    SpinPoker,
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Game::VideoPoker => write!(f, "VideoPoker"),
+            Game::UltimateX(multipliers) => write!(
+                f,
+                "UltimateX {}",
+                multipliers
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Game::SpinPoker => write!(f, "SpinPoker"),
+        }
+    }
+}
+
+impl FromStr for Game {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(' ') {
+            Some(("UltimateX", multipliers)) => multipliers
+                .split(' ')
+                .map(|multiplier| multiplier.parse().map_err(|_| "Malformed record"))
+                .collect::<Result<_, _>>()
+                .map(Game::UltimateX),
+            None if s == "VideoPoker" => Ok(Game::VideoPoker),
+            None if s == "SpinPoker" => Ok(Game::SpinPoker),
+            _ => Err("Unknown game"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Entry {
+    pub game: Game,
+    pub bet: Money,
+    pub round: Round,
+    pub prize: Money,
+    pub jackpot: Money,
+    pub gamble: Option<String>,
+    pub bonus: Vec<BonusRound>,
+    pub credits_before: Money,
+    pub credits_after: Money,
+}
+
+impl Entry {
+    fn replay(
+        &self,
+        game: &mut VideoPoker,
+        spin_poker: &mut SpinPoker,
+        score_board: &ScoreBoard,
+        gambles: &[&dyn Gamble],
+    ) -> Result<(), &'static str> {
+        let mut player = Replay(&self.round.held);
+        let hands = match self.game {
+            Game::SpinPoker => spin_poker.start_seeded(&mut player, self.round.seed),
+            _ => game.start_seeded(&mut player, self.round.hands.len(), self.round.seed),
+        };
+        let round = match self.game {
+            Game::SpinPoker => spin_poker.round(),
+            _ => game.round(),
+        };
+        if round != &self.round {
+            return Err("Cards do not match the seed");
+        }
+        // The meter isn't logged, but a jackpot has to come with a hand that
+        // pays it and such a hand always pays one.
+        let jackpots: Vec<Vec<Card>> = match self.game {
+            Game::SpinPoker => Vec::new(),
+            _ => game
+                .hands()
+                .iter()
+                .filter(|cards| score_board.pays_progressive(self.bet, cards))
+                .cloned()
+                .collect(),
+        };
+        match (jackpots.is_empty(), self.jackpot == Money::ZERO) {
+            (true, false) => return Err("Jackpot without a progressive hand"),
+            (false, true) => return Err("Progressive hand without a jackpot"),
+            _ => {}
+        }
+        let (cost, prize) = match &self.game {
+            Game::VideoPoker => (
+                self.bet,
+                score_board.prize_jackpots(game, self.bet, &hands, &[], &jackpots),
+            ),
+            Game::UltimateX(multipliers) => (
+                UltimateX::wager_of(multipliers.len(), self.bet)
+                    .ok_or("Bet does not fit the credits")?,
+                score_board.prize_jackpots(game, self.bet, &hands, multipliers, &jackpots),
+            ),
+            Game::SpinPoker => (
+                self.bet.saturating_mul(spin_poker.paylines().len()),
+                score_board.prize_lines(self.bet, &hands),
+            ),
+        };
+        let credits = self
+            .credits_before
+            .checked_sub(cost)
+            .and_then(|credits| credits.checked_add(self.jackpot));
+        // A prize the credits can't hold is not paid and is logged as zero.
+        let unpaid = self.prize == Money::ZERO
+            && credits.is_some_and(|credits| credits.checked_add(prize).is_none());
+        let prize = if unpaid { Money::ZERO } else { prize };
+        if prize != self.prize {
+            return Err("Prize does not match the hand");
+        }
+        let payout = match &self.gamble {
+            None if self.bonus.is_empty() => prize,
+            None => return Err("Bonus played without a gamble"),
+            Some(name) => {
+                let gamble = gambles
+                    .iter()
+                    .find(|gamble| gamble.name() == name)
+                    .ok_or("Unknown gamble")?;
+                BonusGame::replay(*gamble, prize, &self.bonus)?.payout()
+            }
+        };
+        match credits.and_then(|credits| credits.checked_add(score_board.capped(payout))) {
+            Some(credits) if credits == self.credits_after => Ok(()),
+            _ => Err("Credits do not balance"),
+        }
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let cards = |cards: &[Card]| {
+            cards
+                .iter()
+                .map(Card::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            self.game,
+            self.round.seed,
+            self.bet,
+            cards(&self.round.dealt),
+            self.round
+                .held
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(" "),
+            self.round
+                .hands
+                .iter()
+                .map(|hand| cards(hand))
+                .collect::<Vec<_>>()
+                .join(","),
+            self.prize,
+            self.jackpot,
+            self.gamble.as_deref().unwrap_or(""),
+            self.bonus
+                .iter()
+                .map(|round| format!(
+                    "{};{};{};{}",
+                    round.choice,
+                    round.guess,
+                    cards(&round.cards),
+                    round.outcome
+                ))
+                .collect::<Vec<_>>()
+                .join(","),
+            self.credits_before,
+            self.credits_after
+        )
+    }
+}
+
+impl FromStr for Entry {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn list<T: FromStr>(s: &str, separator: char) -> Result<Vec<T>, &'static str> {
+            s.split(separator)
+                .filter(|item| !item.is_empty())
+                .map(|item| item.parse().map_err(|_| "Malformed record"))
+                .collect()
+        }
+        let fields: Vec<&str> = s.split('|').collect();
+        // Only video poker was audited before records named their game.
+        let (game, fields) = match fields.len() {
+            11 => (Game::VideoPoker, &fields[..]),
+            _ => (fields[0].parse()?, &fields[1..]),
+        };
+        let [
+            seed,
+            bet,
+            dealt,
+            held,
+            hands,
+            prize,
+            jackpot,
+            gamble,
+            bonus,
+            before,
+            after,
+        ] = fields[..]
+        else {
+            return Err("Malformed record");
+        };
+        Ok(Entry {
+            game,
+            bet: bet.parse()?,
+            round: Round {
+                seed: seed.parse().map_err(|_| match seed.parse::<u64>() {
+                    // Records from before rounds were keyed by the full seed
+                    // were dealt by a different shuffle and can't be replayed.
+                    Ok(_) => "Record was dealt by an older engine",
+                    Err(_) => "Malformed record",
+                })?,
+                dealt: list(dealt, ' ')?,
+                held: list(held, ' ')?,
+                hands: hands
+                    .split(',')
+                    .map(|hand| list(hand, ' '))
+                    .collect::<Result<_, _>>()?,
+            },
+            prize: prize.parse()?,
+            jackpot: jackpot.parse()?,
+            gamble: (!gamble.is_empty()).then(|| gamble.to_string()),
+            bonus: bonus
+                .split(',')
+                .filter(|round| !round.is_empty())
+                .map(|round| {
+                    let [choice, guess, cards, outcome] = round.split(';').collect::<Vec<_>>()[..]
+                    else {
+                        return Err("Malformed record");
+                    };
+                    Ok(BonusRound {
+                        choice: choice.parse()?,
+                        cards: list(cards, ' ')?,
+                        guess: guess.parse()?,
+                        outcome: outcome.parse()?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            credits_before: before.parse()?,
+            credits_after: after.parse()?,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+pub struct AuditLog {
+    file: File,
+    last_hash: String,
+}
+
+#[cfg(feature = "std")]
+impl AuditLog {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let last_hash = match fs::read_to_string(&path) {
+            Ok(log) => log
+                .lines()
+                .last()
+                .and_then(|line| line.rsplit('|').next())
+                .unwrap_or(GENESIS)
+                .to_string(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => GENESIS.to_string(),
+            Err(e) => return Err(e),
+        };
+        Ok(AuditLog {
+            file: OpenOptions::new().create(true).append(true).open(path)?,
+            last_hash,
+        })
+    }
+
+    // Records the credits a session starts from, so the first round of the
+    // session can be checked against them.
+    pub fn open_session(&mut self, credits: Money) -> io::Result<()> {
+        self.write(&format!("{}{}", SESSION, credits))
+    }
+
+    pub fn append(&mut self, entry: &Entry) -> io::Result<()> {
+        self.write(&entry.to_string())
+    }
+
+    fn write(&mut self, line: &str) -> io::Result<()> {
+        let record = format!("{}|{}", self.last_hash, line);
+        let hash = digest(&record);
+        writeln!(self.file, "{}|{}", record, hash)?;
+        self.file.sync_data()?;
+        self.last_hash = hash;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AuditError {
+    pub record: usize,
+    pub reason: &'static str,
+}
+
+impl Display for AuditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Record {}: {}", self.record, self.reason)
+    }
+}
+
+pub fn verify(
+    log: &str,
+    game: &mut VideoPoker,
+    spin_poker: &mut SpinPoker,
+    score_board: &ScoreBoard,
+    gambles: &[&dyn Gamble],
+) -> Result<usize, AuditError> {
+    let mut last_hash = GENESIS;
+    let mut credits = None;
+    let mut records = 0;
+    for (i, line) in log.lines().enumerate() {
+        let fail = |reason| AuditError {
+            record: i + 1,
+            reason,
+        };
+        let (record, hash) = line.rsplit_once('|').ok_or(fail("Malformed record"))?;
+        let (previous, entry) = record.split_once('|').ok_or(fail("Malformed record"))?;
+        if previous != last_hash {
+            return Err(fail("Broken hash chain"));
+        }
+        if digest(record) != hash {
+            return Err(fail("Hash mismatch"));
+        }
+        last_hash = hash;
+        if let Some(session) = entry.strip_prefix(SESSION) {
+            credits = Some(session.parse().map_err(fail)?);
+            continue;
+        }
+        let entry: Entry = entry.parse().map_err(fail)?;
+        if credits.is_some_and(|credits| credits != entry.credits_before) {
+            return Err(fail("Credits do not follow the previous record"));
+        }
+        entry
+            .replay(game, spin_poker, score_board, gambles)
+            .map_err(fail)?;
+        credits = Some(entry.credits_after);
+        records += 1;
+    }
+    Ok(records)
+}
+
+pub(crate) fn digest(record: &str) -> String {
+    Sha256::digest(record.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub(crate) struct Replay<'a>(pub(crate) &'a [usize]);
+
+impl video_poker::Player for Replay<'_> {
+    fn show_cards(&self, _cards: &[Card]) {}
+
+    fn exchange(&self, cards: &[Card]) -> Vec<usize> {
+        (0..cards.len()).filter(|i| !self.0.contains(i)).collect()
+    }
+}
+
+impl spin_poker::Player for Replay<'_> {
+    fn show_grid(&self, _grid: &[Vec<Card>]) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bonus_game::HighLow;
+    use crate::score_board::Rate;
+    use crate::video_poker::{Hand, Rule};
+    use alloc::vec;
+
+    #[cfg(feature = "std")]
+    fn score_board() -> ScoreBoard {
+        ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER)
+    }
+
+    fn play(seed: u64, credits_before: usize) -> Entry {
+        let mut game = VideoPoker::new(Rule::Default52Cards);
+        let hand = game.start_seeded(&mut Replay(&[0, 1]), 1, seed)[0];
+        let prize = hand
+            .map(|hand: Hand| 50 * Rate::JOKER_POKER.by_hand(&hand))
+            .unwrap_or(0);
+        Entry {
+            game: Game::VideoPoker,
+            bet: Money::new(50),
+            round: game.round().clone(),
+            prize: Money::new(prize),
+            jackpot: Money::ZERO,
+            gamble: None,
+            bonus: Vec::new(),
+            credits_before: Money::new(credits_before),
+            credits_after: Money::new(credits_before - 50 + prize),
+        }
+    }
+
+    fn play_ultimate_x(seed: u64, credits_before: usize) -> Entry {
+        let mut game = VideoPoker::new(Rule::Default52Cards);
+        let hands = game.start_seeded(&mut Replay(&[0, 1]), 3, seed);
+        let multipliers = vec![2, 1, 4];
+        let prize = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER).prize_multiplied(
+            &mut game,
+            Money::new(50),
+            &hands,
+            &multipliers,
+        );
+        Entry {
+            game: Game::UltimateX(multipliers),
+            bet: Money::new(50),
+            round: game.round().clone(),
+            prize,
+            jackpot: Money::ZERO,
+            gamble: None,
+            bonus: Vec::new(),
+            credits_before: Money::new(credits_before),
+            credits_after: Money::new(credits_before - 300 + prize.amount()),
+        }
+    }
+
+    fn play_spin_poker(seed: u64, credits_before: usize) -> Entry {
+        let mut spin_poker =
+            SpinPoker::new(Rule::Default52Cards, SpinPoker::default_paylines()).unwrap();
+        let hands = spin_poker.start_seeded(&mut Replay(&[0, 1]), seed);
+        let prize =
+            ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER).prize_lines(Money::new(50), &hands);
+        Entry {
+            game: Game::SpinPoker,
+            bet: Money::new(50),
+            round: spin_poker.round().clone(),
+            prize,
+            jackpot: Money::ZERO,
+            gamble: None,
+            bonus: Vec::new(),
+            credits_before: Money::new(credits_before),
+            credits_after: Money::new(credits_before - 450 + prize.amount()),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn write_log(name: &str, entries: &[Entry]) -> String {
+        let path = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut log = AuditLog::open(&path).unwrap();
+        log.open_session(entries[0].credits_before).unwrap();
+        entries.iter().for_each(|entry| log.append(entry).unwrap());
+        let log = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        log
+    }
+
+    #[cfg(feature = "std")]
+    fn verify_log(log: &str) -> Result<usize, AuditError> {
+        verify_log_with(log, &score_board())
+    }
+
+    #[cfg(feature = "std")]
+    fn verify_log_with(log: &str, score_board: &ScoreBoard) -> Result<usize, AuditError> {
+        let mut game = VideoPoker::new(Rule::Default52Cards);
+        let mut spin_poker =
+            SpinPoker::new(Rule::Default52Cards, SpinPoker::default_paylines()).unwrap();
+        verify(log, &mut game, &mut spin_poker, score_board, &[&HighLow])
+    }
+
+    // A paying round and a score board whose progressive is won by its hand.
+    #[cfg(feature = "std")]
+    fn play_progressive() -> (Entry, ScoreBoard) {
+        let entry = (1..)
+            .map(|seed| play(seed, 1000))
+            .find(|entry| entry.prize > Money::ZERO)
+            .unwrap();
+        let hand = Hand::from_cards(&entry.round.hands[0]).unwrap();
+        let progressive =
+            crate::progressive::Progressive::new(Money::new(1000), 1, hand, Money::new(50));
+        (entry, score_board().with_progressive(progressive))
+    }
+
+    #[test]
+    fn entry_should_parse_what_it_writes() {
+        let mut entry = play(7, 1000);
+        entry.gamble = Some(HighLow.name().to_string());
+        entry.bonus = vec![BonusRound {
+            choice: crate::bonus_game::Choice::DoubleHalf,
+            cards: entry.round.dealt[..1].to_vec(),
+            guess: crate::bonus_game::Guess::Less,
+            outcome: crate::bonus_game::Outcome::Push,
+        }];
+
+        assert!(entry.to_string().parse::<Entry>() == Ok(entry));
+        for entry in [play_ultimate_x(7, 1000), play_spin_poker(7, 1000)] {
+            assert!(entry.to_string().parse::<Entry>() == Ok(entry));
+        }
+    }
+
+    #[test]
+    fn entry_should_read_a_record_without_a_game_as_video_poker() {
+        let entry = play(7, 1000);
+        let written = entry.to_string();
+        let (_, legacy) = written.split_once('|').unwrap();
+
+        assert!(legacy.parse::<Entry>() == Ok(entry));
+    }
+
+    #[test]
+    fn entry_should_reject_a_record_keyed_by_a_short_seed() {
+        let entry = play(7, 1000).to_string();
+        let fields: Vec<&str> = entry.split('|').skip(2).collect();
+
+        assert!(
+            format!("7|{}", fields.join("|")).parse::<Entry>()
+                == Err("Record was dealt by an older engine")
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verify_should_replay_every_game() {
+        let first = play(1, 1000);
+        let second = play_ultimate_x(2, first.credits_after.amount());
+        let third = play_spin_poker(3, second.credits_after.amount());
+        let log = write_log("video-poker-audit-test", &[first, second, third]);
+
+        assert_eq!(verify_log(&log), Ok(3));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verify_should_detect_a_missing_record() {
+        let first = play(1, 1000);
+        let second = play_spin_poker(2, first.credits_after.amount());
+        let third = play(3, second.credits_after.amount());
+        let log = write_log("video-poker-audit-gap-test", &[first, third]);
+
+        assert_eq!(
+            verify_log(&log),
+            Err(AuditError {
+                record: 3,
+                reason: "Credits do not follow the previous record"
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verify_should_detect_an_edited_record() {
+        let first = play(1, 1000);
+        let entry = play(3, first.credits_after.amount());
+        let log = write_log("video-poker-audit-edit-test", &[first, entry.clone()]);
+        let edited = log.replace(
+            &format!("|{}|", entry.credits_after),
+            &format!("|{}|", entry.credits_after.amount() + 1000),
+        );
+
+        assert_eq!(
+            verify_log(&edited).map_err(|e| e.reason),
+            Err("Hash mismatch")
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verify_should_detect_cards_that_do_not_match_the_seed() {
+        let mut entry = play(4, 1000);
+        entry.round.seed = crate::video_poker::Seed::from(5);
+        let log = write_log("video-poker-audit-seed-test", &[entry]);
+
+        assert_eq!(
+            verify_log(&log),
+            Err(AuditError {
+                record: 2,
+                reason: "Cards do not match the seed"
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verify_should_detect_a_forged_jackpot() {
+        let mut entry = play(1, 1000);
+        entry.jackpot = Money::new(5000);
+        entry.credits_after = entry.credits_after.saturating_add(entry.jackpot);
+        let log = write_log("video-poker-audit-jackpot-test", &[entry]);
+
+        assert_eq!(
+            verify_log(&log),
+            Err(AuditError {
+                record: 2,
+                reason: "Jackpot without a progressive hand"
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verify_should_pay_the_jackpot_in_place_of_the_award() {
+        let (entry, score_board) = play_progressive();
+        let mut paid = entry.clone();
+        paid.prize = Money::ZERO;
+        paid.jackpot = Money::new(1000);
+        paid.credits_after = Money::new(1950);
+        let paid = write_log("video-poker-audit-progressive-test", &[paid]);
+        let unpaid = write_log("video-poker-audit-progressive-unpaid-test", &[entry]);
+
+        assert_eq!(verify_log_with(&paid, &score_board), Ok(1));
+        assert_eq!(
+            verify_log_with(&unpaid, &score_board).map_err(|e| e.reason),
+            Err("Progressive hand without a jackpot")
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verify_should_accept_a_prize_the_credits_could_not_hold() {
+        let mut entry = (1..)
+            .map(|seed| play_ultimate_x(seed, 1000))
+            .find(|entry| entry.prize > Money::new(300))
+            .unwrap();
+        entry.prize = Money::ZERO;
+        entry.credits_before = Money::new(usize::MAX);
+        entry.credits_after = Money::new(usize::MAX - 300);
+        let log = write_log("video-poker-audit-unpaid-test", &[entry]);
+
+        assert_eq!(verify_log(&log), Ok(1));
+    }
+}
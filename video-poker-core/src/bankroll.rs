@@ -0,0 +1,244 @@
+use crate::score_board::Rate;
+use crate::video_poker::{Hand, Player, Rule, VideoPoker};
+use alloc::vec::Vec;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// Walks that are still above the minimum after this many rounds are given up.
+const PASSAGE_LIMIT: usize = 1_000_000;
+
+pub struct Session {
+    pub bankroll: usize,
+    pub bet: usize,
+    pub rounds: usize,
+    // Smallest bet the game takes. A bankroll that can't cover `bet` but is
+    // still above it bets what is left.
+    pub minimum: usize,
+}
+
+pub struct Risk {
+    pub sessions: usize,
+    // Share of sessions that fell below the minimum.
+    pub ruin: f64,
+    // Mean rounds until the bankroll falls below the minimum, playing on past
+    // the session length. `None` when a walk is still going at the limit.
+    pub expected_rounds: Option<f64>,
+    // Ending bankrolls of every session, lowest first.
+    pub ending: Vec<usize>,
+}
+
+impl Session {
+    pub fn new(bankroll: usize, bet: usize, rounds: usize) -> Self {
+        Session {
+            bankroll,
+            bet,
+            rounds,
+            minimum: bet,
+        }
+    }
+
+    pub fn with_minimum(self, minimum: usize) -> Self {
+        Session { minimum, ..self }
+    }
+}
+
+impl Risk {
+    pub fn percentile(&self, percent: f64) -> usize {
+        let last = self.ending.len().saturating_sub(1);
+        let index = (last as f64 * percent / 100.0 + 0.5) as usize;
+        self.ending.get(index.min(last)).copied().unwrap_or(0)
+    }
+
+    pub fn mean_ending(&self) -> f64 {
+        self.ending
+            .iter()
+            .map(|bankroll| *bankroll as f64)
+            .sum::<f64>()
+            / self.ending.len().max(1) as f64
+    }
+}
+
+// How often each hand of `Hand::ALL` is made when `player` plays the deals.
+pub fn distribution(rule: Rule, player: &dyn Player, deals: usize, seed: u64) -> Vec<(Hand, f64)> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut game = VideoPoker::new(rule);
+    let mut counts = [0; Hand::ALL.len()];
+    for _ in 0..deals {
+        let dealt = game.deal(rng.next_u64()).to_vec();
+        if let Some(hand) = game
+            .draw(1, player.exchange(&dealt))
+            .unwrap()
+            .pop()
+            .flatten()
+        {
+            counts[Hand::ALL.iter().position(|h| *h == hand).unwrap()] += 1;
+        }
+    }
+    Hand::ALL
+        .iter()
+        .zip(counts)
+        .map(|(hand, count)| (*hand, count as f64 / deals.max(1) as f64))
+        .collect()
+}
+
+pub fn return_to_player(rate: &Rate, distribution: &[(Hand, f64)]) -> f64 {
+    distribution
+        .iter()
+        .map(|(hand, probability)| rate.by_hand(hand) as f64 * probability)
+        .sum()
+}
+
+pub fn risk(
+    rate: &Rate,
+    distribution: &[(Hand, f64)],
+    session: &Session,
+    sessions: usize,
+    seed: u64,
+) -> Risk {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let minimum = session.minimum.max(1);
+    let (mut broke, mut rounds, mut endless) = (0, 0, false);
+    let mut ending: Vec<usize> = (0..sessions)
+        .map(|_| {
+            let mut bankroll = session.bankroll;
+            let mut ended = None;
+            let mut round = 0;
+            while bankroll >= minimum {
+                if round == session.rounds {
+                    ended = Some(bankroll);
+                    // Once a walk has hit the limit, only the session is left to play.
+                    if endless {
+                        break;
+                    }
+                }
+                if round == PASSAGE_LIMIT.max(session.rounds) {
+                    endless = true;
+                    break;
+                }
+                round += 1;
+                let bet = session.bet.min(bankroll);
+                bankroll -= bet;
+                let mut draw: f64 = rng.random();
+                if let Some((hand, _)) = distribution.iter().find(|(_, probability)| {
+                    draw -= probability;
+                    draw < 0.0
+                }) {
+                    bankroll = bankroll.saturating_add(bet.saturating_mul(rate.by_hand(hand)));
+                }
+            }
+            rounds += round;
+            ended.unwrap_or_else(|| {
+                broke += 1;
+                bankroll
+            })
+        })
+        .collect();
+    ending.sort();
+    Risk {
+        sessions,
+        ruin: broke as f64 / sessions.max(1) as f64,
+        expected_rounds: (!endless).then(|| rounds as f64 / sessions.max(1) as f64),
+        ending,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::ChartPlayer;
+
+    #[test]
+    fn risk_should_go_broke_when_nothing_pays() {
+        let distribution = [(Hand::JacksOrBetter, 0.0)];
+        let risk = risk(
+            &Rate::JOKER_POKER,
+            &distribution,
+            &Session::new(220, 50, 10),
+            100,
+            1,
+        );
+
+        assert_eq!(risk.ruin, 1.0);
+        assert_eq!(risk.expected_rounds, Some(4.0));
+        assert!(risk.ending.iter().all(|bankroll| *bankroll == 20));
+    }
+
+    #[test]
+    fn risk_should_never_go_broke_when_every_round_pays_back() {
+        let distribution = [(Hand::JacksOrBetter, 1.0)];
+        let risk = risk(
+            &Rate::JOKER_POKER,
+            &distribution,
+            &Session::new(50, 50, 10),
+            100,
+            1,
+        );
+
+        assert_eq!(risk.ruin, 0.0);
+        assert_eq!(risk.expected_rounds, None);
+        assert_eq!(risk.percentile(50.0), 50);
+    }
+
+    #[test]
+    fn risk_should_bet_what_is_left_down_to_the_minimum() {
+        let distribution = [(Hand::JacksOrBetter, 0.0)];
+        let risk = risk(
+            &Rate::JOKER_POKER,
+            &distribution,
+            &Session::new(250, 100, 10).with_minimum(50),
+            100,
+            1,
+        );
+
+        assert_eq!(risk.ruin, 1.0);
+        assert_eq!(risk.expected_rounds, Some(3.0));
+        assert!(risk.ending.iter().all(|bankroll| *bankroll == 0));
+    }
+
+    #[test]
+    fn risk_should_count_the_rounds_past_the_session() {
+        let distribution = [(Hand::JacksOrBetter, 0.0)];
+        let risk = risk(
+            &Rate::JOKER_POKER,
+            &distribution,
+            &Session::new(500, 50, 2),
+            100,
+            1,
+        );
+
+        assert_eq!(risk.ruin, 0.0);
+        assert_eq!(risk.expected_rounds, Some(10.0));
+        assert!(risk.ending.iter().all(|bankroll| *bankroll == 400));
+    }
+
+    #[test]
+    fn risk_should_sort_the_ending_bankrolls() {
+        let distribution = [(Hand::TwoPair, 0.4)];
+        let risk = risk(
+            &Rate::JOKER_POKER,
+            &distribution,
+            &Session::new(200, 50, 20),
+            200,
+            2,
+        );
+
+        assert!(risk.ending.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(risk.percentile(0.0), risk.ending[0]);
+        assert_eq!(risk.percentile(100.0), risk.ending[199]);
+        assert!(risk.ruin > 0.0 && risk.ruin < 1.0);
+        assert!(risk.expected_rounds.is_some());
+    }
+
+    #[test]
+    fn distribution_should_add_up_with_the_losing_hands() {
+        let distribution = distribution(Rule::Jokers54Cards, &ChartPlayer, 200, 3);
+        let total: f64 = distribution
+            .iter()
+            .map(|(_, probability)| probability)
+            .sum();
+
+        assert_eq!(distribution.len(), Hand::ALL.len());
+        assert!(total > 0.0 && total <= 1.0);
+        assert!(return_to_player(&Rate::JOKER_POKER, &distribution) > 0.0);
+    }
+}
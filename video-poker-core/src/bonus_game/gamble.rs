@@ -0,0 +1,370 @@
+use super::player::{Guess, Outcome};
+use crate::card::{Card, Suit};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+pub trait Gamble {
+    fn name(&self) -> &str;
+
+    fn cards(&self) -> usize;
+
+    fn face_up(&self) -> usize;
+
+    fn guesses(&self) -> Vec<Guess>;
+
+    fn multiplier(&self) -> usize;
+
+    fn judge(&self, cards: &[Card], guess: &Guess) -> Outcome;
+
+    fn hints(&self, face_up: &[Card], unseen: &[Card]) -> Vec<Hint> {
+        self.guesses()
+            .into_iter()
+            .map(|guess| {
+                let outcomes: Vec<Outcome> = unseen
+                    .iter()
+                    .map(|card| {
+                        let cards: Vec<Card> = face_up.iter().chain([card]).cloned().collect();
+                        self.judge(&cards, &guess)
+                    })
+                    .collect();
+                Hint::new(guess, self.multiplier(), &outcomes)
+            })
+            .collect()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hint {
+    pub guess: Guess,
+    pub win: f64,
+    pub push: f64,
+    pub ev: f64,
+}
+
+impl Hint {
+    fn new(guess: Guess, multiplier: usize, outcomes: &[Outcome]) -> Self {
+        let count = |expect: Outcome| outcomes.iter().filter(|o| *o == &expect).count() as f64;
+        let total = outcomes.len().max(1) as f64;
+        let (win, push) = (count(Outcome::Win) / total, count(Outcome::Push) / total);
+        Hint {
+            guess,
+            win,
+            push,
+            ev: win * multiplier as f64 + push,
+        }
+    }
+}
+
+pub struct HighLow;
+
+impl Gamble for HighLow {
+    fn name(&self) -> &str {
+        "High or Low"
+    }
+
+    fn cards(&self) -> usize {
+        1
+    }
+
+    fn face_up(&self) -> usize {
+        0
+    }
+
+    fn guesses(&self) -> Vec<Guess> {
+        vec![Guess::Greater, Guess::Less]
+    }
+
+    fn multiplier(&self) -> usize {
+        2
+    }
+
+    fn judge(&self, cards: &[Card], guess: &Guess) -> Outcome {
+        let v = cards[0].rank.value();
+        let win = if v > 7 {
+            guess == &Guess::Greater
+        } else if v < 7 {
+            guess == &Guess::Less
+        } else {
+            true
+        };
+        if win { Outcome::Win } else { Outcome::Lose }
+    }
+}
+
+pub struct RedBlack;
+
+impl Gamble for RedBlack {
+    fn name(&self) -> &str {
+        "Red or Black"
+    }
+
+    fn cards(&self) -> usize {
+        1
+    }
+
+    fn face_up(&self) -> usize {
+        0
+    }
+
+    fn guesses(&self) -> Vec<Guess> {
+        vec![Guess::Red, Guess::Black]
+    }
+
+    fn multiplier(&self) -> usize {
+        2
+    }
+
+    fn judge(&self, cards: &[Card], guess: &Guess) -> Outcome {
+        let is_red = matches!(cards[0].suit, Suit::Heart | Suit::Diamond);
+        match guess {
+            Guess::Red if is_red => Outcome::Win,
+            Guess::Black if !is_red => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+}
+
+pub struct Suits;
+
+impl Gamble for Suits {
+    fn name(&self) -> &str {
+        "Suit"
+    }
+
+    fn cards(&self) -> usize {
+        1
+    }
+
+    fn face_up(&self) -> usize {
+        0
+    }
+
+    fn guesses(&self) -> Vec<Guess> {
+        [Suit::Heart, Suit::Spade, Suit::Diamond, Suit::Club]
+            .into_iter()
+            .map(Guess::Suit)
+            .collect()
+    }
+
+    fn multiplier(&self) -> usize {
+        4
+    }
+
+    fn judge(&self, cards: &[Card], guess: &Guess) -> Outcome {
+        match guess {
+            Guess::Suit(suit) if suit == &cards[0].suit => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+}
+
+pub struct DealerCard;
+
+impl DealerCard {
+    const FACE_DOWN_CARDS: usize = 4;
+
+    fn ace_high(card: &Card) -> u8 {
+        match card.rank.value() {
+            1 => 14,
+            v => v,
+        }
+    }
+}
+
+impl Gamble for DealerCard {
+    fn name(&self) -> &str {
+        "Beat the Dealer"
+    }
+
+    fn cards(&self) -> usize {
+        1 + DealerCard::FACE_DOWN_CARDS
+    }
+
+    fn face_up(&self) -> usize {
+        1
+    }
+
+    fn guesses(&self) -> Vec<Guess> {
+        (0..DealerCard::FACE_DOWN_CARDS).map(Guess::Pick).collect()
+    }
+
+    fn multiplier(&self) -> usize {
+        2
+    }
+
+    fn hints(&self, face_up: &[Card], unseen: &[Card]) -> Vec<Hint> {
+        self.guesses()
+            .into_iter()
+            .map(|guess| {
+                let outcomes: Vec<Outcome> = unseen
+                    .iter()
+                    .map(|card| self.judge(&[face_up[0].clone(), card.clone()], &Guess::Pick(0)))
+                    .collect();
+                Hint::new(guess, self.multiplier(), &outcomes)
+            })
+            .collect()
+    }
+
+    fn judge(&self, cards: &[Card], guess: &Guess) -> Outcome {
+        let Guess::Pick(i) = guess else {
+            return Outcome::Lose;
+        };
+        let Some(card) = cards[1..].get(*i) else {
+            return Outcome::Lose;
+        };
+        match DealerCard::ace_high(card).cmp(&DealerCard::ace_high(&cards[0])) {
+            Ordering::Greater => Outcome::Win,
+            Ordering::Less => Outcome::Lose,
+            Ordering::Equal => Outcome::Push,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::Rank;
+
+    #[test]
+    fn high_low_should_win_on_seven_for_any_guess() {
+        let cards = [Card {
+            suit: Suit::Heart,
+            rank: Rank::Seven,
+        }];
+
+        assert_eq!(HighLow.judge(&cards, &Guess::Greater), Outcome::Win);
+        assert_eq!(HighLow.judge(&cards, &Guess::Less), Outcome::Win);
+    }
+
+    #[test]
+    fn red_black_should_compare_color_of_suit() {
+        let cards = [Card {
+            suit: Suit::Diamond,
+            rank: Rank::Two,
+        }];
+
+        assert_eq!(RedBlack.judge(&cards, &Guess::Red), Outcome::Win);
+        assert_eq!(RedBlack.judge(&cards, &Guess::Black), Outcome::Lose);
+    }
+
+    #[test]
+    fn suits_should_only_win_on_exact_suit() {
+        let cards = [Card {
+            suit: Suit::Club,
+            rank: Rank::Queen,
+        }];
+
+        assert_eq!(Suits.judge(&cards, &Guess::Suit(Suit::Club)), Outcome::Win);
+        assert_eq!(
+            Suits.judge(&cards, &Guess::Suit(Suit::Spade)),
+            Outcome::Lose
+        );
+    }
+
+    #[test]
+    fn dealer_card_should_treat_ace_as_the_highest_card() {
+        let cards = [
+            Card {
+                suit: Suit::Heart,
+                rank: Rank::King,
+            },
+            Card {
+                suit: Suit::Club,
+                rank: Rank::Ace,
+            },
+            Card {
+                suit: Suit::Club,
+                rank: Rank::Two,
+            },
+        ];
+
+        assert_eq!(DealerCard.judge(&cards, &Guess::Pick(0)), Outcome::Win);
+        assert_eq!(DealerCard.judge(&cards, &Guess::Pick(1)), Outcome::Lose);
+    }
+
+    #[test]
+    fn dealer_card_should_push_on_same_rank() {
+        let cards = [
+            Card {
+                suit: Suit::Heart,
+                rank: Rank::Nine,
+            },
+            Card {
+                suit: Suit::Spade,
+                rank: Rank::Nine,
+            },
+        ];
+
+        assert_eq!(DealerCard.judge(&cards, &Guess::Pick(0)), Outcome::Push);
+    }
+
+    #[test]
+    fn dealer_card_should_lose_on_card_out_of_range() {
+        let cards = [Card {
+            suit: Suit::Heart,
+            rank: Rank::Nine,
+        }];
+
+        assert_eq!(DealerCard.judge(&cards, &Guess::Pick(3)), Outcome::Lose);
+    }
+
+    #[test]
+    fn hints_should_count_the_odds_over_unseen_cards() {
+        let unseen = [
+            Card {
+                suit: Suit::Heart,
+                rank: Rank::Two,
+            },
+            Card {
+                suit: Suit::Diamond,
+                rank: Rank::Three,
+            },
+            Card {
+                suit: Suit::Club,
+                rank: Rank::Four,
+            },
+            Card {
+                suit: Suit::Spade,
+                rank: Rank::Five,
+            },
+        ];
+        let hints = RedBlack.hints(&[], &unseen);
+
+        assert_eq!(hints[0].win, 0.5);
+        assert_eq!(hints[0].ev, 1.0);
+        assert_eq!(hints[1].win, 0.5);
+    }
+
+    #[test]
+    fn dealer_card_hints_should_compare_against_the_dealer_card() {
+        let dealer_card = Card {
+            suit: Suit::Heart,
+            rank: Rank::King,
+        };
+        let unseen = [
+            Card {
+                suit: Suit::Club,
+                rank: Rank::Ace,
+            },
+            Card {
+                suit: Suit::Club,
+                rank: Rank::King,
+            },
+            Card {
+                suit: Suit::Club,
+                rank: Rank::Queen,
+            },
+            Card {
+                suit: Suit::Spade,
+                rank: Rank::Queen,
+            },
+        ];
+        let hints = DealerCard.hints(&[dealer_card], &unseen);
+
+        assert_eq!(hints.len(), 4);
+        assert_eq!(hints[0].win, 0.25);
+        assert_eq!(hints[0].push, 0.25);
+        assert_eq!(hints[0].ev, 0.75);
+    }
+}
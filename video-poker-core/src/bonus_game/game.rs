@@ -1,50 +1,398 @@
-use super::player::{Guess, Player};
+use super::gamble::{Gamble, Hint};
+use super::player::{AsyncPlayer, Awaiting, Blocking, Choice, Guess, Outcome, Player, Turns};
 use crate::card::Card;
 use crate::deck::Deck;
+use crate::money::Money;
+use crate::ready;
+use alloc::vec::Vec;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+type Dealt = (Vec<Card>, Vec<Hint>);
 
 pub struct BonusGame {
     deck: Deck,
+    dealing: Dealing,
+    rng: ChaCha8Rng,
+    history: Vec<Card>,
+    rounds: Vec<BonusRound>,
+    bonus: Bonus,
+    pending: Option<(Choice, Vec<Card>)>,
 }
 
 impl BonusGame {
-    pub fn new() -> Self {
+    pub fn new(dealing: Dealing, rng: &mut impl RngCore) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(rng.next_u64());
+        let mut deck = Deck::default_52_cards();
+        deck.shuffle(&mut rng);
         BonusGame {
-            deck: Deck::default_52_cards(),
+            deck,
+            dealing,
+            rng,
+            history: Vec::new(),
+            rounds: Vec::new(),
+            bonus: Bonus::new(Money::ZERO),
+            pending: None,
         }
     }
 
-    pub fn start(&mut self, player: &mut impl Player) -> Option<usize> {
-        let mut current_round = 0;
-        while player.new_round(current_round) {
-            self.deck.shuffle();
-            let card = self.deck.draw().unwrap();
-            let guess = player.guess();
-            let is_correct = BonusGame::guess_is_correct(&card, &guess);
-            player.round_result(&card, &guess, is_correct);
-            self.deck.push(card);
-            if is_correct {
-                current_round += 1;
-            } else {
-                return None;
+    pub fn history(&self) -> &[Card] {
+        &self.history
+    }
+
+    pub fn rounds(&self) -> &[BonusRound] {
+        &self.rounds
+    }
+
+    pub fn bonus(&self) -> Bonus {
+        self.bonus
+    }
+
+    pub fn start(&mut self, gamble: &dyn Gamble, player: &mut impl Player, prize: Money) -> Bonus {
+        ready::now(self.play(gamble, &Blocking(player), prize))
+    }
+
+    pub async fn start_async(
+        &mut self,
+        gamble: &(dyn Gamble + Sync),
+        player: &mut impl AsyncPlayer,
+        prize: Money,
+    ) -> Bonus {
+        self.play(gamble, &Awaiting(player), prize).await
+    }
+
+    async fn play(
+        &mut self,
+        gamble: &(impl Gamble + ?Sized),
+        turns: &impl Turns,
+        prize: Money,
+    ) -> Bonus {
+        self.open(prize);
+        while !self.bonus.is_lost() {
+            let choice = turns.new_round(&self.bonus, &self.history).await;
+            let Ok(Some((face_up, hints))) = self.choose(gamble, choice) else {
+                break;
+            };
+            let guess = turns.guess(&face_up, &hints).await;
+            let Ok((cards, outcome)) = self.guess(gamble, guess.clone()) else {
+                break;
+            };
+            turns.round_result(&cards, &guess, &outcome).await;
+        }
+        self.bonus
+    }
+
+    pub fn open(&mut self, prize: Money) {
+        if let Some((_, cards)) = self.pending.take() {
+            self.collect(cards);
+        }
+        self.bonus = Bonus::new(prize);
+        self.rounds.clear();
+    }
+
+    pub fn choose(
+        &mut self,
+        gamble: &(impl Gamble + ?Sized),
+        choice: Choice,
+    ) -> Result<Option<Dealt>, &'static str> {
+        if self.pending.is_some() {
+            return Err("Waiting for a guess");
+        }
+        if self.bonus.is_lost() {
+            return Err("Bonus is lost");
+        }
+        match choice {
+            Choice::Collect => return Ok(None),
+            Choice::DoubleHalf => self.bonus.bank_half(),
+            Choice::Double => {}
+        }
+        let cards = self.deal(gamble.cards());
+        let (face_up, face_down) = cards.split_at(gamble.face_up());
+        let unseen: Vec<Card> = self.deck.cards().iter().chain(face_down).cloned().collect();
+        let hints = gamble.hints(face_up, &unseen);
+        let face_up = face_up.to_vec();
+        self.pending = Some((choice, cards));
+        Ok(Some((face_up, hints)))
+    }
+
+    pub fn guess(
+        &mut self,
+        gamble: &(impl Gamble + ?Sized),
+        guess: Guess,
+    ) -> Result<(Vec<Card>, Outcome), &'static str> {
+        let (choice, cards) = self.pending.take().ok_or("No bonus round dealt")?;
+        let outcome = gamble.judge(&cards, &guess);
+        self.rounds.push(BonusRound {
+            choice,
+            cards: cards.clone(),
+            guess,
+            outcome: outcome.clone(),
+        });
+        self.collect(cards.clone());
+        self.bonus.settle(&outcome, gamble.multiplier());
+        Ok((cards, outcome))
+    }
+
+    pub fn replay(
+        gamble: &dyn Gamble,
+        prize: Money,
+        rounds: &[BonusRound],
+    ) -> Result<Bonus, &'static str> {
+        let mut bonus = Bonus::new(prize);
+        for round in rounds {
+            match round.choice {
+                _ if bonus.is_lost() => return Err("Bonus played after it was lost"),
+                Choice::Collect => return Err("Bonus played after it was collected"),
+                Choice::DoubleHalf => bonus.bank_half(),
+                Choice::Double => {}
             }
+            if round.cards.len() != gamble.cards()
+                || gamble.judge(&round.cards, &round.guess) != round.outcome
+            {
+                return Err("Bonus outcome does not match the cards");
+            }
+            bonus.settle(&round.outcome, gamble.multiplier());
         }
-        Some(current_round)
+        Ok(bonus)
     }
 
-    fn guess_is_correct(card: &Card, guess: &Guess) -> bool {
-        let v = card.rank.value();
-        if v > 7 {
-            guess == &Guess::Greater
-        } else if v < 7 {
-            guess == &Guess::Less
-        } else {
-            true
+    fn deal(&mut self, count: usize) -> Vec<Card> {
+        match self.dealing {
+            Dealing::Reshuffle => self.deck.shuffle(&mut self.rng),
+            Dealing::Shoe if self.deck.len() < count => {
+                self.history.drain(..).for_each(|card| self.deck.push(card));
+                self.deck.shuffle(&mut self.rng);
+            }
+            Dealing::Shoe => {}
         }
+        (0..count).map(|_| self.deck.draw().unwrap()).collect()
     }
+
+    fn collect(&mut self, cards: Vec<Card>) {
+        match self.dealing {
+            Dealing::Reshuffle => cards.into_iter().for_each(|card| self.deck.push(card)),
+            Dealing::Shoe => self.history.extend(cards),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BonusRound {
+    pub choice: Choice,
+    pub cards: Vec<Card>,
+    pub guess: Guess,
+    pub outcome: Outcome,
+}
+
+pub enum Dealing {
+    Reshuffle,
+    Shoe,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bonus {
+    pub banked: Money,
+    pub at_risk: Money,
 }
 
-impl Default for BonusGame {
-    fn default() -> Self {
-        Self::new()
+impl Bonus {
+    pub fn new(prize: Money) -> Self {
+        Bonus {
+            banked: Money::ZERO,
+            at_risk: prize,
+        }
+    }
+
+    pub fn payout(&self) -> Money {
+        self.banked.saturating_add(self.at_risk)
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.at_risk == Money::ZERO
+    }
+
+    fn bank_half(&mut self) {
+        let half = Money::new(self.at_risk.amount() / 2);
+        self.banked = self.banked.saturating_add(half);
+        self.at_risk = Money::new(self.at_risk.amount() - half.amount());
+    }
+
+    fn settle(&mut self, outcome: &Outcome, multiplier: usize) {
+        match outcome {
+            Outcome::Win => self.at_risk = self.at_risk.saturating_mul(multiplier),
+            Outcome::Lose => self.at_risk = Money::ZERO,
+            Outcome::Push => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bonus_game::HighLow;
+    use crate::card::Rank;
+    use alloc::vec;
+    use futures::executor::block_on;
+
+    struct Doubler;
+
+    impl AsyncPlayer for Doubler {
+        async fn new_round(&self, bonus: &Bonus, _history: &[Card]) -> Choice {
+            if bonus.at_risk >= Money::new(400) {
+                Choice::Collect
+            } else {
+                Choice::Double
+            }
+        }
+
+        async fn guess(&self, _face_up: &[Card], _hints: &[Hint]) -> Guess {
+            Guess::Greater
+        }
+
+        async fn round_result(&self, _cards: &[Card], _guess: &Guess, _outcome: &Outcome) {}
+    }
+
+    #[test]
+    fn bank_half_should_move_half_of_the_stake_to_banked() {
+        let mut bonus = Bonus::new(Money::new(150));
+        bonus.bank_half();
+
+        assert_eq!(
+            bonus,
+            Bonus {
+                banked: Money::new(75),
+                at_risk: Money::new(75)
+            }
+        );
+    }
+
+    #[test]
+    fn bank_half_should_keep_the_odd_token_at_risk() {
+        let mut bonus = Bonus::new(Money::new(75));
+        bonus.bank_half();
+
+        assert_eq!(
+            bonus,
+            Bonus {
+                banked: Money::new(37),
+                at_risk: Money::new(38)
+            }
+        );
+        assert_eq!(bonus.payout(), Money::new(75));
+    }
+
+    #[test]
+    fn replay_should_settle_the_recorded_rounds() {
+        let round = |choice, rank, guess, outcome| BonusRound {
+            choice,
+            cards: vec![Card {
+                suit: crate::card::Suit::Heart,
+                rank,
+            }],
+            guess,
+            outcome,
+        };
+        let rounds = [
+            round(Choice::Double, Rank::King, Guess::Greater, Outcome::Win),
+            round(Choice::DoubleHalf, Rank::Two, Guess::Greater, Outcome::Lose),
+        ];
+
+        assert_eq!(
+            BonusGame::replay(&HighLow, Money::new(50), &rounds),
+            Ok(Bonus {
+                banked: Money::new(50),
+                at_risk: Money::ZERO
+            })
+        );
+    }
+
+    #[test]
+    fn replay_should_reject_an_outcome_that_does_not_match() {
+        let rounds = [BonusRound {
+            choice: Choice::Double,
+            cards: vec![Card {
+                suit: crate::card::Suit::Heart,
+                rank: Rank::Two,
+            }],
+            guess: Guess::Greater,
+            outcome: Outcome::Win,
+        }];
+
+        assert!(BonusGame::replay(&HighLow, Money::new(50), &rounds).is_err());
+    }
+
+    #[test]
+    fn choose_and_guess_should_play_a_round_step_by_step() {
+        let mut game = BonusGame::new(Dealing::Reshuffle, &mut ChaCha8Rng::seed_from_u64(1));
+        game.open(Money::new(100));
+
+        assert_eq!(
+            game.guess(&HighLow, Guess::Greater),
+            Err("No bonus round dealt")
+        );
+        let (face_up, hints) = game.choose(&HighLow, Choice::DoubleHalf).unwrap().unwrap();
+        assert!(face_up.is_empty());
+        assert_eq!(hints.len(), 2);
+        assert!(game.choose(&HighLow, Choice::Double).is_err());
+        let (cards, outcome) = game.guess(&HighLow, Guess::Greater).unwrap();
+        assert_eq!(game.rounds().len(), 1);
+        assert_eq!(outcome, HighLow.judge(&cards, &Guess::Greater));
+        assert_eq!(game.bonus().banked, Money::new(50));
+        assert_eq!(game.deck.len(), 52);
+    }
+
+    #[test]
+    fn start_async_should_play_until_collected_or_lost() {
+        let mut game = BonusGame::new(Dealing::Shoe, &mut ChaCha8Rng::seed_from_u64(1));
+        let mut player = Doubler;
+        let future = game.start_async(&HighLow, &mut player, Money::new(100));
+        let _: &dyn Send = &future;
+        let bonus = block_on(future);
+
+        assert!(!game.rounds().is_empty());
+        assert!(bonus.is_lost() || bonus.at_risk >= Money::new(400));
+        assert_eq!(
+            BonusGame::replay(&HighLow, Money::new(100), game.rounds()),
+            Ok(bonus)
+        );
+    }
+
+    #[test]
+    fn start_should_end_at_once_without_a_prize() {
+        let mut game = BonusGame::new(Dealing::Shoe, &mut ChaCha8Rng::seed_from_u64(1));
+        let bonus = game.start(
+            &HighLow,
+            &mut crate::strategy::DoubleTimes::new(3),
+            Money::ZERO,
+        );
+
+        assert_eq!(bonus, Bonus::new(Money::ZERO));
+        assert!(game.rounds().is_empty());
+    }
+
+    #[test]
+    fn shoe_should_not_repeat_cards_until_it_runs_out() {
+        let mut game = BonusGame::new(Dealing::Shoe, &mut ChaCha8Rng::seed_from_u64(1));
+        for _ in 0..52 {
+            let cards = game.deal(1);
+            assert!(!game.history().contains(&cards[0]));
+            game.collect(cards);
+        }
+
+        assert_eq!(game.history().len(), 52);
+        game.deal(1);
+        assert!(game.history().is_empty());
+    }
+
+    #[test]
+    fn reshuffle_should_put_the_cards_back() {
+        let mut game = BonusGame::new(Dealing::Reshuffle, &mut ChaCha8Rng::seed_from_u64(1));
+        let cards = game.deal(5);
+        game.collect(cards);
+
+        assert_eq!(game.deck.len(), 52);
+        assert!(game.history().is_empty());
     }
 }
@@ -1,5 +1,7 @@
+mod gamble;
 mod game;
 mod player;
 
-pub use game::BonusGame;
-pub use player::{Guess, Player};
+pub use gamble::{DealerCard, Gamble, HighLow, Hint, RedBlack, Suits};
+pub use game::{Bonus, BonusGame, BonusRound, Dealing};
+pub use player::{AsyncPlayer, Choice, Guess, Outcome, Player};
@@ -1,15 +1,199 @@
-use crate::card::Card;
+use super::gamble::Hint;
+use super::game::Bonus;
+use crate::card::{Card, Suit};
+use core::fmt::{Display, Formatter, Result};
+use core::future::ready;
+use core::str::FromStr;
 
 pub trait Player {
-    fn new_round(&self, current_round: usize) -> bool;
+    fn new_round(&self, bonus: &Bonus, history: &[Card]) -> Choice;
 
-    fn guess(&self) -> Guess;
+    fn guess(&self, face_up: &[Card], hints: &[Hint]) -> Guess;
 
-    fn round_result(&self, card: &Card, guess: &Guess, win: bool);
+    fn round_result(&self, cards: &[Card], guess: &Guess, outcome: &Outcome);
 }
 
-#[derive(PartialEq, Eq)]
+pub trait AsyncPlayer: Sync {
+    fn new_round(&self, bonus: &Bonus, history: &[Card]) -> impl Future<Output = Choice> + Send;
+
+    fn guess(&self, face_up: &[Card], hints: &[Hint]) -> impl Future<Output = Guess> + Send;
+
+    fn round_result(
+        &self,
+        cards: &[Card],
+        guess: &Guess,
+        outcome: &Outcome,
+    ) -> impl Future<Output = ()> + Send;
+}
+
+// The turns of a bonus game, so one game loop serves blocking and async
+// players.
+pub(crate) trait Turns {
+    fn new_round(&self, bonus: &Bonus, history: &[Card]) -> impl Future<Output = Choice>;
+
+    fn guess(&self, face_up: &[Card], hints: &[Hint]) -> impl Future<Output = Guess>;
+
+    fn round_result(
+        &self,
+        cards: &[Card],
+        guess: &Guess,
+        outcome: &Outcome,
+    ) -> impl Future<Output = ()>;
+}
+
+pub(crate) struct Blocking<'a, P: ?Sized>(pub(crate) &'a P);
+
+pub(crate) struct Awaiting<'a, P: ?Sized>(pub(crate) &'a P);
+
+impl<P: Player + ?Sized> Turns for Blocking<'_, P> {
+    fn new_round(&self, bonus: &Bonus, history: &[Card]) -> impl Future<Output = Choice> {
+        ready(self.0.new_round(bonus, history))
+    }
+
+    fn guess(&self, face_up: &[Card], hints: &[Hint]) -> impl Future<Output = Guess> {
+        ready(self.0.guess(face_up, hints))
+    }
+
+    fn round_result(
+        &self,
+        cards: &[Card],
+        guess: &Guess,
+        outcome: &Outcome,
+    ) -> impl Future<Output = ()> {
+        self.0.round_result(cards, guess, outcome);
+        ready(())
+    }
+}
+
+impl<P: AsyncPlayer + ?Sized> Turns for Awaiting<'_, P> {
+    fn new_round(&self, bonus: &Bonus, history: &[Card]) -> impl Future<Output = Choice> {
+        self.0.new_round(bonus, history)
+    }
+
+    fn guess(&self, face_up: &[Card], hints: &[Hint]) -> impl Future<Output = Guess> {
+        self.0.guess(face_up, hints)
+    }
+
+    fn round_result(
+        &self,
+        cards: &[Card],
+        guess: &Guess,
+        outcome: &Outcome,
+    ) -> impl Future<Output = ()> {
+        self.0.round_result(cards, guess, outcome)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Choice {
+    Double,
+    DoubleHalf,
+    Collect,
+}
+
+impl Display for Choice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Double => "Double",
+                Self::DoubleHalf => "Double half",
+                Self::Collect => "Collect",
+            }
+        )
+    }
+}
+
+impl FromStr for Choice {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "Double" => Ok(Self::Double),
+            "Double half" => Ok(Self::DoubleHalf),
+            "Collect" => Ok(Self::Collect),
+            _ => Err("Invalid choice"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Guess {
     Greater,
     Less,
+    Red,
+    Black,
+    Suit(Suit),
+    Pick(usize),
+}
+
+impl Display for Guess {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Greater => write!(f, "Greater"),
+            Self::Less => write!(f, "Less"),
+            Self::Red => write!(f, "Red"),
+            Self::Black => write!(f, "Black"),
+            Self::Suit(suit) => write!(f, "{}", suit),
+            Self::Pick(i) => write!(f, "Card {}", i + 1),
+        }
+    }
+}
+
+impl FromStr for Guess {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "Greater" => Ok(Self::Greater),
+            "Less" => Ok(Self::Less),
+            "Red" => Ok(Self::Red),
+            "Black" => Ok(Self::Black),
+            _ => match s.strip_prefix("Card ") {
+                Some(i) => match i.parse::<usize>() {
+                    Ok(i) if i > 0 => Ok(Self::Pick(i - 1)),
+                    _ => Err("Invalid guess"),
+                },
+                None => s.parse().map(Self::Suit).map_err(|_| "Invalid guess"),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    Win,
+    Lose,
+    Push,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Win => "Win",
+                Self::Lose => "Lose",
+                Self::Push => "Push",
+            }
+        )
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "Win" => Ok(Self::Win),
+            "Lose" => Ok(Self::Lose),
+            "Push" => Ok(Self::Push),
+            _ => Err("Invalid outcome"),
+        }
+    }
 }
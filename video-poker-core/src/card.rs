@@ -1,6 +1,9 @@
-use std::fmt::{Display, Formatter, Result};
+use alloc::string::ToString;
+use core::fmt::{Display, Formatter, Result};
+use core::str::FromStr;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Card {
     pub suit: Suit,
     pub rank: Rank,
@@ -12,7 +15,20 @@ impl Display for Card {
     }
 }
 
-#[derive(Clone, PartialEq, Eq)]
+impl FromStr for Card {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let suit = s.chars().next().ok_or("Invalid card")?;
+        Ok(Card {
+            suit: suit.to_string().parse()?,
+            rank: s[suit.len_utf8()..].parse()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Suit {
     Heart,
     Spade,
@@ -37,7 +53,23 @@ impl Display for Suit {
     }
 }
 
-#[derive(Clone, PartialEq, Eq)]
+impl FromStr for Suit {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "♥" => Ok(Self::Heart),
+            "♠" => Ok(Self::Spade),
+            "♣" => Ok(Self::Club),
+            "♦" => Ok(Self::Diamond),
+            "★" => Ok(Self::Joker),
+            _ => Err("Invalid suit"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rank {
     Ace,
     Two,
@@ -97,3 +129,57 @@ impl Display for Rank {
         )
     }
 }
+
+impl FromStr for Rank {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Self::Ace),
+            "2" => Ok(Self::Two),
+            "3" => Ok(Self::Three),
+            "4" => Ok(Self::Four),
+            "5" => Ok(Self::Five),
+            "6" => Ok(Self::Six),
+            "7" => Ok(Self::Seven),
+            "8" => Ok(Self::Eight),
+            "9" => Ok(Self::Nine),
+            "10" => Ok(Self::Ten),
+            "J" => Ok(Self::Jack),
+            "Q" => Ok(Self::Queen),
+            "K" => Ok(Self::King),
+            _ => Err("Invalid rank"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn any_card() -> impl proptest::strategy::Strategy<Value = Card> {
+    use proptest::prelude::*;
+    let suits = [
+        Suit::Heart,
+        Suit::Spade,
+        Suit::Diamond,
+        Suit::Club,
+        Suit::Joker,
+    ];
+    let ranks = [
+        Rank::Ace,
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+    ];
+    (0..suits.len(), 0..ranks.len()).prop_map(move |(suit, rank)| Card {
+        suit: suits[suit].clone(),
+        rank: ranks[rank].clone(),
+    })
+}
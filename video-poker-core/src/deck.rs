@@ -1,5 +1,6 @@
 use crate::card::{Card, Rank, Suit};
-use rand::{rng, seq::SliceRandom};
+use alloc::vec::Vec;
+use rand::RngCore;
 
 pub struct Deck {
     deck: Vec<Card>,
@@ -62,8 +63,32 @@ impl Deck {
         }
     }
 
-    pub fn shuffle(&mut self) {
-        self.deck.shuffle(&mut rng());
+    pub fn len(&self) -> usize {
+        self.deck.len()
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.deck
+    }
+
+    // Fisher-Yates from the top of the deck down, drawing each swap position
+    // from `next_u32` with rejection sampling, so any published RNG stream
+    // gives the same order on every platform.
+    pub fn shuffle(&mut self, rng: &mut impl RngCore) {
+        for i in (1..self.deck.len()).rev() {
+            self.deck.swap(i, Deck::uniform(rng, i + 1));
+        }
+    }
+
+    fn uniform(rng: &mut impl RngCore, bound: usize) -> usize {
+        let bound = bound as u64;
+        let zone = (1 << 32) - (1 << 32) % bound;
+        loop {
+            let value = rng.next_u32() as u64;
+            if value < zone {
+                return (value % bound) as usize;
+            }
+        }
     }
 }
 
@@ -129,4 +154,31 @@ mod test {
                 }
         );
     }
+
+    #[test]
+    fn shuffle_should_give_the_same_order_for_the_same_seed() {
+        use rand::SeedableRng;
+        let shuffled = |seed| {
+            let mut deck = Deck::default_52_cards();
+            deck.shuffle(&mut rand_chacha::ChaCha8Rng::seed_from_u64(seed));
+            deck.inner()
+        };
+
+        assert!(shuffled(1) == shuffled(1));
+        assert!(shuffled(1) != shuffled(2));
+        assert!(
+            Deck::default_52_cards()
+                .inner()
+                .iter()
+                .all(|card| shuffled(1).contains(card))
+        );
+    }
+
+    #[test]
+    fn len_should_count_the_cards_left() {
+        let mut deck = Deck::default_52_cards();
+        deck.draw().unwrap();
+
+        assert_eq!(deck.len(), 51);
+    }
 }
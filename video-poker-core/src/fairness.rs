@@ -0,0 +1,148 @@
+use crate::audit::{Replay, digest};
+use crate::video_poker::{Round, Rule, Seed, VideoPoker};
+use alloc::format;
+use alloc::string::String;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+pub struct FairRound {
+    server_seed: String,
+    nonce: u64,
+}
+
+impl FairRound {
+    pub fn new(nonce: u64, rng: &mut impl RngCore) -> Self {
+        let mut server_seed = [0; 32];
+        rng.fill_bytes(&mut server_seed);
+        FairRound {
+            server_seed: server_seed
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+            nonce,
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn commitment(&self) -> String {
+        digest(&self.server_seed)
+    }
+
+    pub fn seed(&self, client_seed: &str) -> Seed {
+        round_seed(&self.server_seed, client_seed, self.nonce)
+    }
+
+    pub fn reveal(self) -> String {
+        self.server_seed
+    }
+}
+
+pub fn round_seed(server_seed: &str, client_seed: &str, nonce: u64) -> Seed {
+    let record = format!("{}:{}:{}", server_seed, client_seed, nonce);
+    Seed::from(<[u8; 32]>::from(Sha256::digest(record.as_bytes())))
+}
+
+pub fn verify(
+    commitment: &str,
+    server_seed: &str,
+    client_seed: &str,
+    nonce: u64,
+    rule: Rule,
+    round: &Round,
+) -> Result<(), &'static str> {
+    if digest(server_seed) != commitment {
+        return Err("Server seed does not match the commitment");
+    }
+    if round.seed != round_seed(server_seed, client_seed, nonce) {
+        return Err("Round was not dealt from these seeds");
+    }
+    let mut game = VideoPoker::new(rule);
+    game.start_seeded(&mut Replay(&round.held), round.hands.len(), round.seed);
+    if game.round() != round {
+        return Err("Cards do not match the seeds");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::video_poker::SuperTimesPay;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn play(fair: &FairRound, client_seed: &str) -> Round {
+        let mut game = VideoPoker::new(Rule::Jokers54Cards).with_feature(SuperTimesPay::new(0.5));
+        game.start_seeded(&mut Replay(&[2, 4]), 1, fair.seed(client_seed));
+        game.round().clone()
+    }
+
+    #[test]
+    fn verify_should_accept_a_round_dealt_from_the_revealed_seed() {
+        let fair = FairRound::new(7, &mut ChaCha8Rng::seed_from_u64(7));
+        let commitment = fair.commitment();
+        let round = play(&fair, "lucky");
+        let server_seed = fair.reveal();
+
+        assert_eq!(
+            verify(
+                &commitment,
+                &server_seed,
+                "lucky",
+                7,
+                Rule::Jokers54Cards,
+                &round
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn verify_should_reject_a_seed_other_than_the_committed_one() {
+        let fair = FairRound::new(1, &mut ChaCha8Rng::seed_from_u64(1));
+        let commitment = fair.commitment();
+        let round = play(&fair, "lucky");
+
+        assert_eq!(
+            verify(
+                &commitment,
+                &FairRound::new(1, &mut ChaCha8Rng::seed_from_u64(2)).reveal(),
+                "lucky",
+                1,
+                Rule::Jokers54Cards,
+                &round
+            ),
+            Err("Server seed does not match the commitment")
+        );
+    }
+
+    #[test]
+    fn verify_should_reject_swapped_cards() {
+        let fair = FairRound::new(1, &mut ChaCha8Rng::seed_from_u64(1));
+        let commitment = fair.commitment();
+        let mut round = play(&fair, "lucky");
+        round.hands[0].swap(0, 1);
+        let server_seed = fair.reveal();
+
+        assert_eq!(
+            verify(
+                &commitment,
+                &server_seed,
+                "lucky",
+                1,
+                Rule::Jokers54Cards,
+                &round
+            ),
+            Err("Cards do not match the seeds")
+        );
+    }
+
+    #[test]
+    fn round_seed_should_depend_on_the_client_seed() {
+        assert_ne!(round_seed("server", "a", 0), round_seed("server", "b", 0));
+        assert_ne!(round_seed("server", "a", 0), round_seed("server", "a", 1));
+    }
+}
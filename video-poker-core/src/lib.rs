@@ -1,7 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod audit;
+pub mod bankroll;
 pub mod bonus_game;
 mod card;
 mod deck;
+pub mod fairness;
+mod money;
+#[cfg(feature = "std")]
+pub mod progressive;
+mod ready;
+pub mod recall;
 pub mod score_board;
+#[cfg(feature = "std")]
+pub mod selftest;
+pub mod spin_poker;
+pub mod strategy;
+pub mod ultimate_x;
 pub mod video_poker;
 
 pub use card::{Card, Rank, Suit};
+pub use money::Money;
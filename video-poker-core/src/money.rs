@@ -0,0 +1,81 @@
+use core::fmt::{Display, Formatter, Result};
+use core::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Money(usize);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn new(amount: usize) -> Self {
+        Money(amount)
+    }
+
+    pub fn amount(&self) -> usize {
+        self.0
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+
+    pub fn checked_mul(self, times: usize) -> Option<Money> {
+        self.0.checked_mul(times).map(Money)
+    }
+
+    pub fn saturating_add(self, other: Money) -> Money {
+        Money(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_mul(self, times: usize) -> Money {
+        Money(self.0.saturating_mul(times))
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Money {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        s.parse().map(Money).map_err(|_| "Invalid amount")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checked_operations_should_return_none_instead_of_wrapping() {
+        assert_eq!(Money::new(usize::MAX).checked_add(Money::new(1)), None);
+        assert_eq!(Money::ZERO.checked_sub(Money::new(1)), None);
+        assert_eq!(
+            Money::new(5).checked_add(Money::new(7)),
+            Some(Money::new(12))
+        );
+        assert_eq!(Money::new(usize::MAX / 2).checked_mul(3), None);
+        assert_eq!(Money::new(50).checked_mul(6), Some(Money::new(300)));
+    }
+
+    #[test]
+    fn saturating_operations_should_stop_at_the_maximum() {
+        assert_eq!(
+            Money::new(usize::MAX / 2).saturating_mul(3),
+            Money::new(usize::MAX)
+        );
+        assert_eq!(
+            Money::new(usize::MAX).saturating_add(Money::new(1)),
+            Money::new(usize::MAX)
+        );
+    }
+}
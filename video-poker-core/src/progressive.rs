@@ -0,0 +1,228 @@
+use crate::card::{Card, Suit};
+use crate::money::Money;
+use crate::video_poker::Hand;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Clone)]
+pub struct Progressive {
+    meter: Arc<Mutex<Meter>>,
+}
+
+struct Meter {
+    seed: Money,
+    percent: usize,
+    hand: Hand,
+    bet: Money,
+    hundredths: usize,
+}
+
+impl Progressive {
+    pub fn new(seed: Money, percent: usize, hand: Hand, bet: Money) -> Self {
+        Progressive {
+            meter: Arc::new(Mutex::new(Meter {
+                seed,
+                percent,
+                hand,
+                bet,
+                hundredths: seed.amount().saturating_mul(100),
+            })),
+        }
+    }
+
+    pub fn amount(&self) -> Money {
+        Money::new(self.meter().hundredths / 100)
+    }
+
+    pub fn hand(&self) -> Hand {
+        self.meter().hand
+    }
+
+    pub fn bet(&self) -> Money {
+        self.meter().bet
+    }
+
+    pub fn contribute(&self, wager: Money) {
+        let mut meter = self.meter();
+        meter.hundredths = meter
+            .hundredths
+            .saturating_add(wager.amount().saturating_mul(meter.percent));
+    }
+
+    // A natural top hand at the full bet pays the meter in place of its award.
+    pub fn pays(&self, bet: Money, cards: &[Card]) -> bool {
+        self.meter().pays(bet, cards)
+    }
+
+    pub fn hit(&self, bet: Money, cards: &[Card]) -> Option<Money> {
+        let mut meter = self.meter();
+        if !meter.pays(bet, cards) {
+            return None;
+        }
+        let amount = Money::new(meter.hundredths / 100);
+        meter.hundredths = meter
+            .seed
+            .amount()
+            .saturating_mul(100)
+            .saturating_add(meter.hundredths % 100);
+        Some(amount)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let meter = self.meter();
+        fs::write(
+            path,
+            format!(
+                "{}|{}|{}|{}|{}",
+                meter.seed, meter.percent, meter.hand, meter.bet, meter.hundredths
+            ),
+        )
+    }
+
+    // Restores the whole meter, configuration included, into every clone.
+    pub fn load(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let invalid = |_| io::Error::new(io::ErrorKind::InvalidData, "Invalid meter");
+        let saved = fs::read_to_string(path)?;
+        let fields: Vec<&str> = saved.trim().split('|').collect();
+        let [seed, percent, hand, bet, hundredths] = fields[..] else {
+            return Err(invalid("Invalid meter"));
+        };
+        let meter = Meter {
+            seed: seed.parse().map_err(invalid)?,
+            percent: percent.parse().map_err(|_| invalid("Invalid percent"))?,
+            hand: hand.parse().map_err(invalid)?,
+            bet: bet.parse().map_err(invalid)?,
+            hundredths: hundredths.parse().map_err(|_| invalid("Invalid meter"))?,
+        };
+        *self.meter() = meter;
+        Ok(())
+    }
+
+    fn meter(&self) -> MutexGuard<'_, Meter> {
+        self.meter.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Meter {
+    fn pays(&self, bet: Money, cards: &[Card]) -> bool {
+        let natural = cards.iter().all(|card| card.suit != Suit::Joker);
+        bet >= self.bet && natural && Hand::from_cards(cards) == Some(self.hand)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::Rank;
+
+    fn royal_flush(suit: Suit) -> Vec<Card> {
+        [Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten]
+            .into_iter()
+            .map(|rank| Card {
+                suit: suit.clone(),
+                rank,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn contribute_should_add_percent_of_wager() {
+        let progressive = Progressive::new(Money::new(1000), 2, Hand::RoyalFlush, Money::new(500));
+        progressive.contribute(Money::new(50));
+        progressive.contribute(Money::new(50));
+
+        assert_eq!(progressive.amount(), Money::new(1002));
+    }
+
+    #[test]
+    fn hit_should_pay_and_reset_to_seed() {
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+        progressive.contribute(Money::new(1000));
+
+        assert_eq!(
+            progressive.hit(Money::new(500), &royal_flush(Suit::Heart)),
+            Some(Money::new(1100))
+        );
+        assert_eq!(progressive.amount(), Money::new(1000));
+    }
+
+    #[test]
+    fn pays_should_leave_the_meter_alone() {
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+        progressive.contribute(Money::new(1000));
+
+        assert!(progressive.pays(Money::new(500), &royal_flush(Suit::Heart)));
+        assert!(!progressive.pays(Money::new(200), &royal_flush(Suit::Heart)));
+        assert_eq!(progressive.amount(), Money::new(1100));
+    }
+
+    #[test]
+    fn hit_should_require_the_bet() {
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+
+        assert_eq!(
+            progressive.hit(Money::new(200), &royal_flush(Suit::Heart)),
+            None
+        );
+    }
+
+    #[test]
+    fn hit_should_require_a_natural_hand() {
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+        let mut cards = royal_flush(Suit::Heart);
+        cards[0] = Card {
+            suit: Suit::Joker,
+            rank: Rank::Ace,
+        };
+
+        assert_eq!(progressive.hit(Money::new(500), &cards), None);
+    }
+
+    #[test]
+    fn clone_should_share_the_meter() {
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+        progressive.clone().contribute(Money::new(100));
+
+        assert_eq!(progressive.amount(), Money::new(1010));
+    }
+
+    #[test]
+    fn load_should_restore_the_saved_meter_and_configuration() {
+        let path =
+            std::env::temp_dir().join(format!("video-poker-progressive-{}", std::process::id()));
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+        progressive.contribute(Money::new(255));
+        progressive.save(&path).unwrap();
+
+        let restored = Progressive::new(Money::new(1), 1, Hand::StraightFlush, Money::new(50));
+        let shared = restored.clone();
+        restored.load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(shared.hand(), Hand::RoyalFlush);
+        assert_eq!(shared.bet(), Money::new(500));
+        assert_eq!(
+            shared.hit(Money::new(500), &royal_flush(Suit::Heart)),
+            Some(Money::new(1025))
+        );
+        shared.contribute(Money::new(100));
+        assert_eq!(shared.amount(), Money::new(1010));
+    }
+
+    #[test]
+    fn load_should_reject_a_malformed_file() {
+        let path = std::env::temp_dir().join(format!(
+            "video-poker-progressive-malformed-{}",
+            std::process::id()
+        ));
+        fs::write(&path, "1025").unwrap();
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+        let loaded = progressive.load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.is_err());
+        assert_eq!(progressive.amount(), Money::new(1000));
+    }
+}
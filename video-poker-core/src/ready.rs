@@ -0,0 +1,11 @@
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+
+// Runs a game loop driven by a blocking player. Its turns are ready futures,
+// so the loop finishes on the first poll.
+pub(crate) fn now<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("Blocking players never wait"),
+    }
+}
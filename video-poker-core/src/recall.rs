@@ -0,0 +1,95 @@
+use crate::bonus_game::BonusRound;
+use crate::money::Money;
+use crate::video_poker::Round;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    pub bet: Money,
+    pub token_before: Money,
+    pub token_after: Money,
+    pub round: Round,
+    pub bonus: Vec<BonusRound>,
+}
+
+pub struct Recall {
+    capacity: usize,
+    records: VecDeque<Record>,
+}
+
+impl Recall {
+    pub fn new(capacity: usize) -> Self {
+        Recall {
+            capacity,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, record: Record) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_back();
+        }
+        self.records.push_front(record);
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn get(&self, games_ago: usize) -> Option<&Record> {
+        self.records.get(games_ago)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Record> {
+        self.records.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    fn record(bet: usize) -> Record {
+        Record {
+            bet: Money::new(bet),
+            token_before: Money::ZERO,
+            token_after: Money::ZERO,
+            round: Round::default(),
+            bonus: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn get_should_return_the_latest_game_first() {
+        let mut recall = Recall::new(10);
+        recall.push(record(50));
+        recall.push(record(100));
+
+        assert_eq!(recall.get(0).unwrap().bet, Money::new(100));
+        assert_eq!(recall.get(1).unwrap().bet, Money::new(50));
+    }
+
+    #[test]
+    fn push_should_drop_the_oldest_game_when_full() {
+        let mut recall = Recall::new(2);
+        [50, 100, 200]
+            .into_iter()
+            .for_each(|bet| recall.push(record(bet)));
+
+        assert_eq!(recall.len(), 2);
+        assert_eq!(
+            recall.iter().map(|record| record.bet).collect::<Vec<_>>(),
+            vec![Money::new(200), Money::new(100)]
+        );
+    }
+}
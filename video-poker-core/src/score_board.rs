@@ -1,46 +1,245 @@
-use crate::video_poker::Hand;
+use crate::bonus_game::Bonus;
+use crate::card::Card;
+use crate::money::Money;
+#[cfg(feature = "std")]
+use crate::progressive::Progressive;
+use crate::ultimate_x::UltimateX;
+use crate::video_poker::{Hand, VideoPoker};
+use alloc::vec::Vec;
 
 pub struct ScoreBoard {
-    token: usize,
+    token: Money,
     rate: Rate,
-    bonus_base: usize,
+    win_cap: Option<Money>,
+    #[cfg(feature = "std")]
+    progressive: Option<Progressive>,
+    // Hands of the current round the progressive was credited for.
+    jackpots: Vec<Vec<Card>>,
+    ultimate_x: Option<UltimateX>,
 }
 
 impl ScoreBoard {
-    pub fn new(token: usize, rate: Rate, bonus_base: usize) -> Self {
+    pub fn new(token: Money, rate: Rate) -> Self {
         Self {
             token,
             rate,
-            bonus_base,
+            win_cap: None,
+            #[cfg(feature = "std")]
+            progressive: None,
+            jackpots: Vec::new(),
+            ultimate_x: None,
         }
     }
 
-    pub fn token(&self) -> usize {
+    pub fn with_win_cap(mut self, win_cap: Money) -> Self {
+        self.win_cap = Some(win_cap);
+        self
+    }
+
+    pub fn win_cap(&self) -> Option<Money> {
+        self.win_cap
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_progressive(mut self, progressive: Progressive) -> Self {
+        self.progressive = Some(progressive);
+        self
+    }
+
+    #[cfg(feature = "std")]
+    pub fn progressive(&self) -> Option<&Progressive> {
+        self.progressive.as_ref()
+    }
+
+    pub fn with_ultimate_x(mut self, ultimate_x: UltimateX) -> Self {
+        self.ultimate_x = Some(ultimate_x);
+        self
+    }
+
+    pub fn ultimate_x(&self) -> Option<&UltimateX> {
+        self.ultimate_x.as_ref()
+    }
+
+    pub fn rate(&self) -> &Rate {
+        &self.rate
+    }
+
+    pub fn token(&self) -> Money {
         self.token
     }
 
-    pub fn prize(&self, base: usize, hand: &Hand, bonus_rounds: Option<usize>) -> usize {
-        base * self.rate.by_hand(hand)
-            * bonus_rounds
-                .map(|r| self.bonus_base.pow(r as u32))
-                .unwrap_or(1)
+    pub fn capped(&self, win: Money) -> Money {
+        self.win_cap.map_or(win, |cap| win.min(cap))
+    }
+
+    pub fn prize(&self, base: Money, hand: &Hand) -> Money {
+        self.capped(self.pay(base, hand))
     }
 
-    pub fn cost(&mut self, cost: usize) -> Result<(), &'static str> {
+    pub fn cost(&mut self, cost: Money) -> Result<(), &'static str> {
         match self.token.checked_sub(cost) {
             Some(c) => {
                 self.token = c;
+                self.jackpots.clear();
+                #[cfg(feature = "std")]
+                if let Some(progressive) = self.progressive.as_ref() {
+                    progressive.contribute(cost);
+                }
                 Ok(())
             }
             None => Err("No enough token."),
         }
     }
 
-    pub fn apply(&mut self, base: usize, hand: Hand, bonus_rounds: usize) {
-        self.token += self.prize(base, &hand, Some(bonus_rounds));
+    pub fn apply(&mut self, bonus: Bonus) -> Result<Money, &'static str> {
+        self.apply_win(bonus.payout())
+    }
+
+    // Credits a win that was not played through the double up.
+    pub fn apply_win(&mut self, win: Money) -> Result<Money, &'static str> {
+        self.credit(self.capped(win))
+    }
+
+    // Every hand pays its rate, then goes through the features of the game
+    // that dealt it.
+    pub fn prize_hands(&self, game: &mut VideoPoker, base: Money, hands: &[Option<Hand>]) -> Money {
+        self.prize_multiplied(game, base, hands, &[])
+    }
+
+    // Pays like `prize_hands` with every hand times its multiplier first.
+    pub fn prize_multiplied(
+        &self,
+        game: &mut VideoPoker,
+        base: Money,
+        hands: &[Option<Hand>],
+        multipliers: &[usize],
+    ) -> Money {
+        self.prize_jackpots(game, base, hands, multipliers, &self.jackpots)
+    }
+
+    // Pays like `prize_multiplied` with the progressive credited for
+    // `jackpots` instead of the hands of this round.
+    pub(crate) fn prize_jackpots(
+        &self,
+        game: &mut VideoPoker,
+        base: Money,
+        hands: &[Option<Hand>],
+        multipliers: &[usize],
+        jackpots: &[Vec<Card>],
+    ) -> Money {
+        self.capped(self.pay_hands(game, base, hands, multipliers, jackpots))
+    }
+
+    // Every payline pays its own rate, as Spin Poker has no features.
+    pub fn prize_lines(&self, base: Money, hands: &[Option<Hand>]) -> Money {
+        self.capped(
+            hands
+                .iter()
+                .flatten()
+                .map(|hand| self.prize(base, hand))
+                .fold(Money::ZERO, Money::saturating_add),
+        )
+    }
+
+    // Pays like `prize_hands` with every hand times its Ultimate X multiplier,
+    // then moves the multipliers on to the next round.
+    pub fn apply_hands(
+        &mut self,
+        game: &mut VideoPoker,
+        base: Money,
+        hands: &[Option<Hand>],
+    ) -> Result<Money, &'static str> {
+        let multipliers = self
+            .ultimate_x
+            .as_ref()
+            .map_or(&[][..], |ux| ux.multipliers());
+        let prize = self.prize_multiplied(game, base, hands, multipliers);
+        let prize = self.credit(prize)?;
+        if let Some(ultimate_x) = self.ultimate_x.as_mut() {
+            ultimate_x.advance(hands);
+        }
+        Ok(prize)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn apply_progressive(
+        &mut self,
+        base: Money,
+        cards: &[Card],
+    ) -> Result<Option<Money>, &'static str> {
+        let Some(progressive) = self.progressive.as_ref() else {
+            return Ok(None);
+        };
+        // Check the credit first, so a failed payout leaves the meter alone.
+        if self.token.checked_add(progressive.amount()).is_none() {
+            return Err("Too many tokens.");
+        }
+        match progressive.hit(base, cards) {
+            Some(jackpot) => {
+                self.credit(jackpot)?;
+                self.jackpots.push(cards.to_vec());
+                Ok(Some(jackpot))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn pay(&self, base: Money, hand: &Hand) -> Money {
+        base.saturating_mul(self.rate.by_hand(hand))
+    }
+
+    fn pay_hands(
+        &self,
+        game: &mut VideoPoker,
+        base: Money,
+        hands: &[Option<Hand>],
+        multipliers: &[usize],
+        jackpots: &[Vec<Card>],
+    ) -> Money {
+        // The progressive replaces the award of every hand it was credited for.
+        let mut unmatched = jackpots.to_vec();
+        let replaced: Vec<bool> = (0..hands.len())
+            .map(|i| {
+                game.hands()
+                    .get(i)
+                    .and_then(|cards| unmatched.iter().position(|jackpot| jackpot == cards))
+                    .map(|position| unmatched.remove(position))
+                    .is_some()
+            })
+            .collect();
+        hands
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !replaced[*i])
+            .filter_map(|(i, hand)| {
+                hand.as_ref().map(|hand| {
+                    let multiplier = multipliers.get(i).copied().unwrap_or(1);
+                    let prize = self.pay(base, hand).saturating_mul(multiplier);
+                    Money::new(game.payout(i, prize.amount()))
+                })
+            })
+            .fold(Money::ZERO, Money::saturating_add)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn pays_progressive(&self, base: Money, cards: &[Card]) -> bool {
+        self.progressive
+            .as_ref()
+            .is_some_and(|progressive| progressive.pays(base, cards))
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn pays_progressive(&self, _base: Money, _cards: &[Card]) -> bool {
+        false
+    }
+
+    fn credit(&mut self, win: Money) -> Result<Money, &'static str> {
+        self.token = self.token.checked_add(win).ok_or("Too many tokens.")?;
+        Ok(win)
     }
 }
 
+#[derive(Clone)]
 pub struct Rate {
     pub royal_flush: usize,
     pub five_of_a_kind: usize,
@@ -55,7 +254,20 @@ pub struct Rate {
 }
 
 impl Rate {
-    fn by_hand(&self, hand: &Hand) -> usize {
+    pub const JOKER_POKER: Rate = Rate {
+        royal_flush: 500,
+        five_of_a_kind: 200,
+        straight_flush: 120,
+        four_of_a_kind: 50,
+        full_house: 10,
+        flush: 7,
+        straight: 5,
+        three_of_a_kind: 3,
+        two_pair: 2,
+        jacks_or_better: 1,
+    };
+
+    pub fn by_hand(&self, hand: &Hand) -> usize {
         match hand {
             Hand::RoyalFlush => self.royal_flush,
             Hand::StraightFlush => self.straight_flush,
@@ -74,59 +286,302 @@ impl Rate {
 #[cfg(test)]
 mod test {
     use super::*;
+    #[cfg(feature = "std")]
+    use crate::audit::Replay;
+    use crate::video_poker::{Rule, SuperTimesPay};
+    use proptest::prelude::*;
 
-    const DEFAULT_RATE: Rate = Rate {
-        royal_flush: 500,
-        five_of_a_kind: 200,
-        straight_flush: 120,
-        four_of_a_kind: 50,
-        full_house: 10,
-        flush: 7,
-        straight: 5,
-        three_of_a_kind: 3,
-        two_pair: 2,
-        jacks_or_better: 1,
-    };
+    fn game() -> VideoPoker {
+        VideoPoker::new(Rule::Default52Cards)
+    }
 
     #[test]
     fn cost_should_subtract_token_with_cost() {
-        let mut board = ScoreBoard::new(100, DEFAULT_RATE, 2);
+        let mut board = ScoreBoard::new(Money::new(100), Rate::JOKER_POKER);
 
-        board.cost(50).unwrap();
-        assert_eq!(board.token(), 50);
+        board.cost(Money::new(50)).unwrap();
+        assert_eq!(board.token(), Money::new(50));
     }
 
     #[test]
     fn cost_should_return_err_if_no_enough_token() {
-        let mut board = ScoreBoard::new(100, DEFAULT_RATE, 2);
+        let mut board = ScoreBoard::new(Money::new(100), Rate::JOKER_POKER);
 
-        assert!(board.cost(500).is_err());
+        assert!(board.cost(Money::new(500)).is_err());
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn apply_should_add_token_base_on_result() {
+    fn cost_should_feed_the_progressive() {
+        let progressive = Progressive::new(Money::new(1000), 10, Hand::RoyalFlush, Money::new(500));
+        let mut board = ScoreBoard::new(Money::new(100), Rate::JOKER_POKER)
+            .with_progressive(progressive.clone());
+
+        board.cost(Money::new(50)).unwrap();
+        assert_eq!(progressive.amount(), Money::new(1005));
+    }
+
+    // A dealt hand and a score board whose progressive is won by it.
+    #[cfg(feature = "std")]
+    fn progressive_round(token: Money) -> (ScoreBoard, VideoPoker, Hand) {
+        let mut game = game();
+        let hand = (1..)
+            .find_map(|seed| game.start_seeded(&mut Replay(&[0, 1]), 1, seed)[0])
+            .unwrap();
+        let progressive = Progressive::new(Money::new(1000), 1, hand, Money::new(50));
+        let board = ScoreBoard::new(token, Rate::JOKER_POKER).with_progressive(progressive);
+        (board, game, hand)
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn apply_progressive_should_replace_the_award_it_credits() {
+        let (mut board, mut game, hand) = progressive_round(Money::ZERO);
+        let cards = game.cards().to_vec();
+
+        assert_eq!(
+            board.apply_progressive(Money::new(50), &cards),
+            Ok(Some(Money::new(1000)))
+        );
+        assert_eq!(
+            board.prize_hands(&mut game, Money::new(50), &[Some(hand)]),
+            Money::ZERO
+        );
+        board.cost(Money::new(50)).unwrap();
+        assert_eq!(
+            board.prize_hands(&mut game, Money::new(50), &[Some(hand)]),
+            board.prize(Money::new(50), &hand)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn apply_progressive_should_leave_the_award_when_it_fails() {
+        let (mut board, mut game, hand) = progressive_round(Money::new(usize::MAX - 10));
+        let cards = game.cards().to_vec();
+
+        assert!(board.apply_progressive(Money::new(50), &cards).is_err());
+        assert_eq!(
+            board.prize_hands(&mut game, Money::new(50), &[Some(hand)]),
+            board.prize(Money::new(50), &hand)
+        );
+        assert!(board.prize(Money::new(50), &hand) > Money::ZERO);
+    }
+
+    #[test]
+    fn prize_should_multiply_base_by_rate_of_hand() {
         const BASE: usize = 50;
         let test_cases = [
-            ((Hand::RoyalFlush, 0), 500),
-            ((Hand::FiveOfAKind, 0), 200),
-            ((Hand::StraightFlush, 0), 120),
-            ((Hand::FourOfAKind, 0), 50),
-            ((Hand::FullHouse, 0), 10),
-            ((Hand::Flush, 0), 7),
-            ((Hand::Straight, 0), 5),
-            ((Hand::ThreeOfAKind, 0), 3),
-            ((Hand::TwoPair, 0), 2),
-            ((Hand::JacksOrBetter, 0), 1),
-            ((Hand::JacksOrBetter, 1), 2),
-            ((Hand::JacksOrBetter, 2), 4),
-            ((Hand::JacksOrBetter, 3), 8),
+            (Hand::RoyalFlush, 500),
+            (Hand::FiveOfAKind, 200),
+            (Hand::StraightFlush, 120),
+            (Hand::FourOfAKind, 50),
+            (Hand::FullHouse, 10),
+            (Hand::Flush, 7),
+            (Hand::Straight, 5),
+            (Hand::ThreeOfAKind, 3),
+            (Hand::TwoPair, 2),
+            (Hand::JacksOrBetter, 1),
         ];
 
-        for ((hand, round), expect) in test_cases {
-            let mut board = ScoreBoard::new(0, DEFAULT_RATE, 2);
-            board.apply(BASE, hand, round);
+        for (hand, expect) in test_cases {
+            let board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER);
+
+            assert_eq!(
+                board.prize(Money::new(BASE), &hand),
+                Money::new(BASE * expect)
+            );
+        }
+    }
+
+    #[test]
+    fn apply_should_add_token_base_on_bonus_payout() {
+        let test_cases = [
+            (
+                Bonus {
+                    banked: Money::new(0),
+                    at_risk: Money::new(200),
+                },
+                200,
+            ),
+            (
+                Bonus {
+                    banked: Money::new(50),
+                    at_risk: Money::new(200),
+                },
+                250,
+            ),
+            (
+                Bonus {
+                    banked: Money::new(50),
+                    at_risk: Money::new(0),
+                },
+                50,
+            ),
+        ];
+
+        for (bonus, expect) in test_cases {
+            let mut board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER);
+            board.apply(bonus).unwrap();
+
+            assert_eq!(board.token(), Money::new(expect));
+        }
+    }
+
+    #[test]
+    fn apply_hands_should_sum_prize_of_every_hand() {
+        let mut board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER);
+
+        assert_eq!(
+            board.apply_hands(
+                &mut game(),
+                Money::new(50),
+                &[Some(Hand::Flush), None, Some(Hand::TwoPair)]
+            ),
+            Ok(Money::new(450))
+        );
+        assert_eq!(board.token(), Money::new(450));
+    }
+
+    #[test]
+    fn apply_hands_should_use_and_advance_ultimate_x_multipliers() {
+        let mut board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER)
+            .with_ultimate_x(UltimateX::new(Rate::JOKER_POKER, 2));
+        let hands = [Some(Hand::TwoPair), Some(Hand::JacksOrBetter)];
+
+        assert_eq!(
+            board.apply_hands(&mut game(), Money::new(50), &hands),
+            Ok(Money::new(150))
+        );
+        assert_eq!(
+            board.apply_hands(&mut game(), Money::new(50), &hands),
+            Ok(Money::new(250))
+        );
+        assert_eq!(board.token(), Money::new(400));
+    }
 
-            assert_eq!(board.token(), BASE * expect);
+    #[test]
+    fn apply_hands_should_go_through_the_features_of_the_game() {
+        let mut game = game().with_feature(SuperTimesPay::new(1.0));
+        game.deal(1);
+        let multiplier = game.payout(0, 1);
+        let mut board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER)
+            .with_ultimate_x(UltimateX::new(Rate::JOKER_POKER, 2));
+
+        assert_eq!(
+            board.apply_hands(&mut game, Money::new(50), &[Some(Hand::TwoPair), None]),
+            Ok(Money::new(100 * multiplier))
+        );
+    }
+
+    #[test]
+    fn apply_hands_should_pay_1x_past_the_ultimate_x_hands() {
+        let mut board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER)
+            .with_ultimate_x(UltimateX::new(Rate::JOKER_POKER, 1));
+        board
+            .apply_hands(&mut game(), Money::new(50), &[Some(Hand::TwoPair)])
+            .unwrap();
+
+        assert_eq!(
+            board.apply_hands(&mut game(), Money::new(50), &[None, Some(Hand::Flush)]),
+            Ok(Money::new(350))
+        );
+    }
+
+    #[test]
+    fn prize_hands_should_leave_out_the_ultimate_x_multipliers() {
+        let mut board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER)
+            .with_ultimate_x(UltimateX::new(Rate::JOKER_POKER, 1));
+        board
+            .apply_hands(&mut game(), Money::new(50), &[Some(Hand::TwoPair)])
+            .unwrap();
+
+        assert_eq!(
+            board.prize_hands(&mut game(), Money::new(50), &[Some(Hand::Flush)]),
+            Money::new(350)
+        );
+    }
+
+    #[test]
+    fn win_cap_should_limit_the_prize_and_payout_of_a_round() {
+        let mut board =
+            ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER).with_win_cap(Money::new(1000));
+
+        assert_eq!(
+            board.prize(Money::new(50), &Hand::RoyalFlush),
+            Money::new(1000)
+        );
+        assert_eq!(
+            board.apply(Bonus::new(Money::new(4000))),
+            Ok(Money::new(1000))
+        );
+        assert_eq!(board.token(), Money::new(1000));
+    }
+
+    #[test]
+    fn prize_lines_should_sum_every_line_under_the_cap() {
+        let board = ScoreBoard::new(Money::ZERO, Rate::JOKER_POKER).with_win_cap(Money::new(1000));
+        let lines = [Some(Hand::Flush), None, Some(Hand::TwoPair)];
+
+        assert_eq!(board.prize_lines(Money::new(50), &lines), Money::new(450));
+        assert_eq!(
+            board.prize_lines(Money::new(50), &[Some(Hand::FullHouse); 3]),
+            Money::new(1000)
+        );
+    }
+
+    #[test]
+    fn apply_win_should_credit_the_capped_win() {
+        let mut board =
+            ScoreBoard::new(Money::new(100), Rate::JOKER_POKER).with_win_cap(Money::new(1000));
+
+        assert_eq!(board.apply_win(Money::new(250)), Ok(Money::new(250)));
+        assert_eq!(board.apply_win(Money::new(4000)), Ok(Money::new(1000)));
+        assert_eq!(board.token(), Money::new(1350));
+    }
+
+    #[test]
+    fn apply_should_fail_instead_of_overflowing_the_token() {
+        let mut board = ScoreBoard::new(Money::new(usize::MAX), Rate::JOKER_POKER);
+
+        assert!(board.apply(Bonus::new(Money::new(1))).is_err());
+        assert_eq!(board.token(), Money::new(usize::MAX));
+    }
+
+    proptest! {
+        #[test]
+        fn arithmetic_should_saturate_or_fail_instead_of_overflowing(
+            token: usize,
+            base: usize,
+            bonus: usize,
+            rate: usize,
+            cap: usize,
+        ) {
+            let rate = || Rate {
+                royal_flush: rate,
+                ..Rate::JOKER_POKER
+            };
+            let mut score_board = ScoreBoard::new(Money::new(token), rate())
+                .with_ultimate_x(UltimateX::new(rate(), 2))
+                .with_win_cap(Money::new(cap));
+            let hands = [Some(Hand::RoyalFlush), Some(Hand::RoyalFlush)];
+
+            prop_assert!(score_board.prize(Money::new(base), &Hand::RoyalFlush) <= Money::new(cap));
+            for _ in 0..2 {
+                let before = score_board.token();
+                match score_board.apply_hands(&mut game(), Money::new(base), &hands) {
+                    Ok(prize) => {
+                        prop_assert!(prize <= Money::new(cap));
+                        prop_assert_eq!(before.checked_add(prize), Some(score_board.token()));
+                    }
+                    Err(_) => prop_assert_eq!(score_board.token(), before),
+                }
+            }
+            let before = score_board.token();
+            if score_board.apply(Bonus::new(Money::new(bonus))).is_err() {
+                prop_assert_eq!(score_board.token(), before);
+            }
+            prop_assert!(score_board.token() >= Money::new(token));
         }
     }
 }
@@ -0,0 +1,276 @@
+use crate::card::Card;
+use crate::video_poker::{self, Hand, Rule, VideoPoker};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+const SIGNIFICANCE: f64 = 0.001;
+const MIN_EXPECTED: f64 = 5.0;
+
+pub struct Check {
+    pub name: String,
+    pub chi_square: f64,
+    pub degrees: usize,
+    pub p_value: f64,
+}
+
+impl Check {
+    fn new(name: String, observed: &[usize], expected: &[f64], constraints: usize) -> Self {
+        let (observed, expected) = pool(observed, expected);
+        let chi_square = observed
+            .iter()
+            .zip(&expected)
+            .map(|(o, e)| (*o as f64 - e).powi(2) / e)
+            .sum();
+        let degrees = expected.len().saturating_sub(constraints).max(1);
+        Check {
+            name,
+            chi_square,
+            degrees,
+            p_value: p_value(chi_square, degrees),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.p_value >= SIGNIFICANCE
+    }
+}
+
+pub fn run(rule: Rule, trials: usize, seed: u64) -> Vec<Check> {
+    vec![
+        positions(rule, trials, seed),
+        hands(rule, trials, seed),
+        draws(rule, trials / 10, seed),
+        redraws(rule, trials / 10, seed),
+    ]
+}
+
+pub fn positions(rule: Rule, trials: usize, seed: u64) -> Check {
+    let cards = rule.create_deck().cards().to_vec();
+    let n = cards.len();
+    let mut observed = vec![0; n * n];
+    let mut seeds = ChaCha8Rng::seed_from_u64(seed);
+    for _ in 0..trials {
+        let mut deck = rule.create_deck();
+        deck.shuffle(&mut ChaCha8Rng::seed_from_u64(seeds.random()));
+        deck.cards()
+            .iter()
+            .enumerate()
+            .for_each(|(position, card)| observed[position * n + index(&cards, card)] += 1);
+    }
+    let expected = vec![trials as f64 / n as f64; n * n];
+    // Every position holds one card and every card takes one position, so
+    // both the row and the column totals are fixed: (n - 1)² degrees.
+    Check::new(
+        format!("Card positions ({} shuffles)", trials),
+        &observed,
+        &expected,
+        2 * n - 1,
+    )
+}
+
+pub fn hands(rule: Rule, trials: usize, seed: u64) -> Check {
+    let mut observed = vec![0; Hand::ALL.len() + 1];
+    let mut seeds = ChaCha8Rng::seed_from_u64(seed);
+    for _ in 0..trials {
+        let mut deck = rule.create_deck();
+        deck.shuffle(&mut ChaCha8Rng::seed_from_u64(seeds.random()));
+        let cards: Vec<Card> = (0..5).map(|_| deck.draw().unwrap()).collect();
+        observed[category(Hand::from_cards(&cards))] += 1;
+    }
+    let counts = hand_counts(rule);
+    let total: usize = counts.iter().sum();
+    let expected: Vec<f64> = counts
+        .iter()
+        .map(|count| trials as f64 * *count as f64 / total as f64)
+        .collect();
+    Check::new(
+        format!("Hand frequencies ({} deals)", trials),
+        &observed,
+        &expected,
+        1,
+    )
+}
+
+pub fn draws(rule: Rule, rounds: usize, seed: u64) -> Check {
+    let cards = rule.create_deck().cards().to_vec();
+    let n = cards.len();
+    let mut observed = vec![0; n * Drawer::HANDS];
+    let mut seeds = ChaCha8Rng::seed_from_u64(seed);
+    let mut game = VideoPoker::new(rule);
+    for _ in 0..rounds {
+        game.start_seeded(&mut Drawer, Drawer::HANDS, seeds.random::<u64>());
+        game.hands().iter().enumerate().for_each(|(hand, drawn)| {
+            Drawer::DISCARD
+                .iter()
+                .for_each(|i| observed[hand * n + index(&cards, &drawn[*i])] += 1)
+        });
+    }
+    let expected = vec![(rounds * Drawer::DISCARD.len()) as f64 / n as f64; n * Drawer::HANDS];
+    Check::new(
+        format!("Drawn cards ({} rounds)", rounds),
+        &observed,
+        &expected,
+        Drawer::HANDS,
+    )
+}
+
+// Discards stay out of the pile until the draw is over, so a single redrawn
+// discard fails the check.
+pub fn redraws(rule: Rule, rounds: usize, seed: u64) -> Check {
+    let mut redrawn = 0;
+    let mut seeds = ChaCha8Rng::seed_from_u64(seed);
+    let mut game = VideoPoker::new(rule);
+    for _ in 0..rounds {
+        game.start_seeded(&mut Drawer, Drawer::HANDS, seeds.random::<u64>());
+        let dealt = &game.round().dealt;
+        game.hands().iter().for_each(|drawn| {
+            Drawer::DISCARD.iter().for_each(|i| {
+                if Drawer::DISCARD.iter().any(|j| dealt[*j] == drawn[*i]) {
+                    redrawn += 1;
+                }
+            })
+        });
+    }
+    Check {
+        name: format!("Redrawn discards ({} rounds)", rounds),
+        chi_square: redrawn as f64,
+        degrees: 0,
+        p_value: if redrawn == 0 { 1.0 } else { 0.0 },
+    }
+}
+
+pub fn hand_counts(rule: Rule) -> Vec<usize> {
+    let cards = rule.create_deck().cards().to_vec();
+    let n = cards.len();
+    let mut counts = vec![0; Hand::ALL.len() + 1];
+    let mut hand = cards[..5].to_vec();
+    for a in 0..n {
+        hand[0] = cards[a].clone();
+        for b in a + 1..n {
+            hand[1] = cards[b].clone();
+            for c in b + 1..n {
+                hand[2] = cards[c].clone();
+                for d in c + 1..n {
+                    hand[3] = cards[d].clone();
+                    for card in &cards[d + 1..] {
+                        hand[4] = card.clone();
+                        counts[category(Hand::from_cards(&hand))] += 1;
+                    }
+                }
+            }
+        }
+    }
+    counts
+}
+
+pub fn category(hand: Option<Hand>) -> usize {
+    hand.and_then(|hand| Hand::ALL.iter().position(|h| *h == hand))
+        .unwrap_or(Hand::ALL.len())
+}
+
+fn index(cards: &[Card], card: &Card) -> usize {
+    cards.iter().position(|c| c == card).unwrap()
+}
+
+fn pool(observed: &[usize], expected: &[f64]) -> (Vec<usize>, Vec<f64>) {
+    let (mut pooled_observed, mut pooled_expected) = (Vec::new(), Vec::new());
+    let (mut rare_observed, mut rare_expected) = (0, 0.0);
+    observed.iter().zip(expected).for_each(|(o, e)| {
+        if *e >= MIN_EXPECTED {
+            pooled_observed.push(*o);
+            pooled_expected.push(*e);
+        } else {
+            rare_observed += o;
+            rare_expected += e;
+        }
+    });
+    if rare_expected > 0.0 {
+        pooled_observed.push(rare_observed);
+        pooled_expected.push(rare_expected);
+    }
+    (pooled_observed, pooled_expected)
+}
+
+fn p_value(chi_square: f64, degrees: usize) -> f64 {
+    let k = degrees as f64;
+    let z = ((chi_square / k).cbrt() - (1.0 - 2.0 / (9.0 * k))) / (2.0 / (9.0 * k)).sqrt();
+    erfc(z / std::f64::consts::SQRT_2) / 2.0
+}
+
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let y = t
+        * (-x * x - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0.0 { y } else { 2.0 - y }
+}
+
+struct Drawer;
+
+impl Drawer {
+    const HANDS: usize = 3;
+    const DISCARD: [usize; 2] = [1, 3];
+}
+
+impl video_poker::Player for Drawer {
+    fn show_cards(&self, _cards: &[Card]) {}
+
+    fn exchange(&self, _cards: &[Card]) -> Vec<usize> {
+        Drawer::DISCARD.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn p_value_should_follow_the_chi_square_distribution() {
+        assert!((p_value(9.342, 10) - 0.5).abs() < 0.01);
+        assert!((p_value(18.307, 10) - 0.05).abs() < 0.005);
+        assert!(p_value(100.0, 10) < SIGNIFICANCE);
+    }
+
+    #[test]
+    fn check_should_fail_on_skewed_counts() {
+        let check = Check::new(String::new(), &[600, 400], &[500.0, 500.0], 1);
+
+        assert!(!check.passed());
+    }
+
+    #[test]
+    fn pool_should_merge_rare_categories() {
+        let (observed, expected) = pool(&[1, 2, 100], &[1.5, 2.5, 100.0]);
+
+        assert_eq!(observed, vec![100, 3]);
+        assert_eq!(expected, vec![100.0, 4.0]);
+    }
+
+    #[test]
+    fn shuffle_should_put_every_card_anywhere_uniformly() {
+        let check = positions(Rule::Default52Cards, 20_000, 1);
+
+        assert_eq!(check.degrees, 51 * 51);
+        assert!(check.passed());
+    }
+
+    #[test]
+    fn exchange_should_not_skew_the_draws() {
+        assert!(draws(Rule::Jokers54Cards, 5_000, 1).passed());
+    }
+
+    #[test]
+    fn exchange_should_never_redraw_a_discard() {
+        let check = redraws(Rule::Jokers54Cards, 5_000, 1);
+
+        assert_eq!(check.chi_square, 0.0);
+        assert!(check.passed());
+    }
+}
@@ -0,0 +1,216 @@
+use super::player::Player;
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::video_poker::{Hand, Round, Rule, Seed};
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::RngCore;
+
+pub type Payline = [usize; 5];
+
+pub struct SpinPoker {
+    rule: Rule,
+    deck: Deck,
+    paylines: Vec<Payline>,
+    round: Round,
+}
+
+impl SpinPoker {
+    const ROWS: usize = 3;
+    const MIDDLE_ROW: usize = 1;
+
+    pub fn new(rule: Rule, paylines: Vec<Payline>) -> Result<Self, &'static str> {
+        if paylines.is_empty() {
+            return Err("No paylines");
+        }
+        if paylines
+            .iter()
+            .any(|line| line.iter().any(|row| *row >= SpinPoker::ROWS))
+        {
+            return Err("Invalid payline");
+        }
+        Ok(SpinPoker {
+            deck: rule.create_deck(),
+            rule,
+            paylines,
+            round: Round::default(),
+        })
+    }
+
+    pub fn default_paylines() -> Vec<Payline> {
+        vec![
+            [1, 1, 1, 1, 1],
+            [0, 0, 0, 0, 0],
+            [2, 2, 2, 2, 2],
+            [0, 1, 2, 1, 0],
+            [2, 1, 0, 1, 2],
+            [0, 0, 1, 2, 2],
+            [2, 2, 1, 0, 0],
+            [1, 0, 0, 0, 1],
+            [1, 2, 2, 2, 1],
+        ]
+    }
+
+    pub fn paylines(&self) -> &[Payline] {
+        &self.paylines
+    }
+
+    pub fn round(&self) -> &Round {
+        &self.round
+    }
+
+    pub fn grid(&self) -> &[Vec<Card>] {
+        &self.round.hands
+    }
+
+    pub fn start(&mut self, player: &mut impl Player, rng: &mut impl RngCore) -> Vec<Option<Hand>> {
+        self.start_seeded(player, rng.next_u64())
+    }
+
+    pub fn start_seeded(
+        &mut self,
+        player: &mut impl Player,
+        seed: impl Into<Seed>,
+    ) -> Vec<Option<Hand>> {
+        let seed = seed.into();
+        self.deck = self.rule.create_deck();
+        self.deck.shuffle(&mut seed.rng());
+        let players_deck: Vec<Card> = (0..5).map(|_| self.deck.draw().unwrap()).collect();
+        player.show_cards(&players_deck);
+
+        let indice_to_exchange = player.exchange(&players_deck);
+        self.round = Round {
+            seed,
+            dealt: players_deck.clone(),
+            held: (0..players_deck.len())
+                .filter(|i| !indice_to_exchange.contains(i))
+                .collect(),
+            hands: self.spin(players_deck, &indice_to_exchange),
+        };
+        player.show_grid(&self.round.hands);
+
+        let hands = self
+            .lines()
+            .iter()
+            .map(|cards| Hand::from_cards(cards))
+            .collect();
+        self.push_back_to_deck();
+        hands
+    }
+
+    pub fn lines(&self) -> Vec<Vec<Card>> {
+        self.paylines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .enumerate()
+                    .map(|(column, row)| self.round.hands[*row][column].clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn spin(&mut self, players_deck: Vec<Card>, indice_to_exchange: &[usize]) -> Vec<Vec<Card>> {
+        let mut grid: Vec<Vec<Card>> = (0..SpinPoker::ROWS)
+            .map(|row| {
+                players_deck
+                    .iter()
+                    .enumerate()
+                    .map(|(column, card)| {
+                        if row == SpinPoker::MIDDLE_ROW || !indice_to_exchange.contains(&column) {
+                            card.clone()
+                        } else {
+                            self.deck.draw().unwrap()
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        indice_to_exchange
+            .iter()
+            .for_each(|column| grid[SpinPoker::MIDDLE_ROW][*column] = self.deck.draw().unwrap());
+        players_deck
+            .into_iter()
+            .enumerate()
+            .filter(|(column, _)| indice_to_exchange.contains(column))
+            .for_each(|(_, card)| self.deck.push(card));
+        grid
+    }
+
+    fn push_back_to_deck(&mut self) {
+        let columns = self.round.hands[0].len();
+        let mut cards: Vec<Card> = Vec::new();
+        (0..columns).for_each(|column| {
+            (0..SpinPoker::ROWS).for_each(|row| {
+                let card = &self.round.hands[row][column];
+                if !cards.contains(card) {
+                    cards.push(card.clone());
+                }
+            })
+        });
+        cards.into_iter().for_each(|card| self.deck.push(card));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn spun(indice_to_exchange: &[usize]) -> (SpinPoker, Vec<Card>) {
+        let mut game = SpinPoker::new(Rule::Default52Cards, SpinPoker::default_paylines()).unwrap();
+        game.deck.shuffle(&mut ChaCha8Rng::seed_from_u64(1));
+        let players_deck: Vec<Card> = (0..5).map(|_| game.deck.draw().unwrap()).collect();
+        game.round.hands = game.spin(players_deck.clone(), indice_to_exchange);
+        (game, players_deck)
+    }
+
+    #[test]
+    fn spin_should_keep_held_cards_on_every_row() {
+        let (game, dealt) = spun(&[1, 3]);
+
+        for column in [0, 2, 4] {
+            for row in 0..3 {
+                assert!(game.grid()[row][column] == dealt[column]);
+            }
+        }
+    }
+
+    #[test]
+    fn spin_should_redeal_every_other_position() {
+        let (game, dealt) = spun(&[0, 1, 2, 3, 4]);
+        let cards: Vec<&Card> = game.grid().iter().flatten().collect();
+
+        for (i, card) in cards.iter().enumerate() {
+            assert!(!cards[i + 1..].contains(card));
+            assert!(!dealt.contains(card));
+        }
+    }
+
+    #[test]
+    fn push_back_to_deck_should_restore_the_full_deck() {
+        let (mut game, _) = spun(&[0, 2]);
+        game.push_back_to_deck();
+
+        assert_eq!(game.deck.len(), 52);
+    }
+
+    #[test]
+    fn new_should_reject_a_payline_off_the_grid() {
+        let paylines = vec![[1, 1, 1, 1, 1], [0, 1, 3, 1, 0]];
+
+        assert!(SpinPoker::new(Rule::Default52Cards, paylines).is_err());
+        assert!(SpinPoker::new(Rule::Default52Cards, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn lines_should_follow_the_paylines() {
+        let (game, _) = spun(&[0, 1, 2, 3, 4]);
+        let lines = game.lines();
+
+        assert_eq!(lines.len(), 9);
+        assert!(lines[0] == game.grid()[1]);
+        assert!(lines[3][2] == game.grid()[2][2]);
+    }
+}
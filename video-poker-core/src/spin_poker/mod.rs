@@ -0,0 +1,5 @@
+mod game;
+mod player;
+
+pub use game::{Payline, SpinPoker};
+pub use player::Player;
@@ -0,0 +1,7 @@
+use crate::card::Card;
+use crate::video_poker;
+use alloc::vec::Vec;
+
+pub trait Player: video_poker::Player {
+    fn show_grid(&self, grid: &[Vec<Card>]);
+}
@@ -0,0 +1,185 @@
+use crate::card::{Card, Suit};
+use crate::video_poker::Hand;
+use alloc::vec::Vec;
+
+type Pattern = (&'static str, fn(&[&Card]) -> bool);
+
+// Best first. Jokers are always held and count towards every pattern.
+const CHART: [Pattern; 20] = [
+    ("Royal flush", |cards| made(cards, 5, Hand::RoyalFlush)),
+    ("Five of a kind", |cards| made(cards, 5, Hand::FiveOfAKind)),
+    ("Straight flush", |cards| {
+        made(cards, 5, Hand::StraightFlush)
+    }),
+    ("Four of a kind", |cards| made(cards, 4, Hand::FourOfAKind)),
+    ("4 to a royal flush", |cards| {
+        cards.len() == 4 && suited(cards) && royal(cards)
+    }),
+    ("Full house", |cards| made(cards, 5, Hand::FullHouse)),
+    ("Flush", |cards| made(cards, 5, Hand::Flush)),
+    ("Straight", |cards| made(cards, 5, Hand::Straight)),
+    ("Three of a kind", |cards| {
+        made(cards, 3, Hand::ThreeOfAKind)
+    }),
+    ("4 to a straight flush", |cards| {
+        cards.len() == 4 && suited(cards) && connected(cards)
+    }),
+    ("Two pair", |cards| made(cards, 4, Hand::TwoPair)),
+    ("High pair", |cards| {
+        cards.len() == 2 && !distinct(cards) && high(cards)
+    }),
+    ("3 to a royal flush", |cards| {
+        cards.len() == 3 && suited(cards) && royal(cards)
+    }),
+    ("4 to a flush", |cards| cards.len() == 4 && suited(cards)),
+    ("Low pair", |cards| cards.len() == 2 && !distinct(cards)),
+    ("4 to a straight", |cards| {
+        cards.len() == 4 && connected(cards)
+    }),
+    ("2 suited high cards", |cards| {
+        cards.len() == 2 && suited(cards) && high(cards)
+    }),
+    ("3 to a straight flush", |cards| {
+        cards.len() == 3 && suited(cards) && connected(cards)
+    }),
+    ("2 high cards", |cards| {
+        cards.len() == 2 && distinct(cards) && high(cards)
+    }),
+    ("1 high card", |cards| cards.len() == 1 && high(cards)),
+];
+
+pub struct Situation {
+    pub name: &'static str,
+    pub held: Vec<usize>,
+}
+
+pub fn classify(cards: &[Card]) -> Situation {
+    let jokers: Vec<usize> = (0..cards.len())
+        .filter(|i| cards[*i].suit == Suit::Joker)
+        .collect();
+    let others: Vec<usize> = (0..cards.len()).filter(|i| !jokers.contains(i)).collect();
+    let holds: Vec<Vec<usize>> = (0..1 << others.len())
+        .map(|mask: usize| {
+            let mut held: Vec<usize> = (0..others.len())
+                .filter(|i| mask & 1 << i != 0)
+                .map(|i| others[i])
+                .chain(jokers.iter().copied())
+                .collect();
+            held.sort();
+            held
+        })
+        .collect();
+    CHART
+        .iter()
+        .find_map(|(name, matches)| {
+            holds
+                .iter()
+                .find(|held| matches(&held.iter().map(|i| &cards[*i]).collect::<Vec<_>>()))
+                .map(|held| Situation {
+                    name,
+                    held: held.clone(),
+                })
+        })
+        .unwrap_or(Situation {
+            name: "Discard all",
+            held: jokers,
+        })
+}
+
+fn made(cards: &[&Card], len: usize, hand: Hand) -> bool {
+    cards.len() == len
+        && Hand::from_cards(&cards.iter().map(|card| (*card).clone()).collect::<Vec<_>>())
+            == Some(hand)
+}
+
+fn ranks<'a>(cards: &'a [&Card]) -> impl Iterator<Item = u8> + 'a {
+    cards
+        .iter()
+        .filter(|card| card.suit != Suit::Joker)
+        .map(|card| card.rank.value())
+}
+
+fn suited(cards: &[&Card]) -> bool {
+    let mut suits = cards
+        .iter()
+        .filter(|card| card.suit != Suit::Joker)
+        .map(|card| &card.suit);
+    match suits.next() {
+        Some(first) => suits.all(|suit| suit == first),
+        None => true,
+    }
+}
+
+fn distinct(cards: &[&Card]) -> bool {
+    let ranks: Vec<u8> = ranks(cards).collect();
+    cards.len() == ranks.len() && (1..ranks.len()).all(|i| !ranks[..i].contains(&ranks[i]))
+}
+
+fn royal(cards: &[&Card]) -> bool {
+    ranks(cards).all(|rank| matches!(rank, 1 | 10..=13))
+}
+
+// Fits in five consecutive ranks, with the ace either low or high.
+fn connected(cards: &[&Card]) -> bool {
+    let fits = |ace: u8| {
+        let (min, max) = ranks(cards)
+            .map(|rank| if rank == 1 { ace } else { rank })
+            .fold((u8::MAX, 0), |(min, max), rank| {
+                (min.min(rank), max.max(rank))
+            });
+        max < min.saturating_add(5)
+    };
+    distinct(cards) && (fits(1) || fits(14))
+}
+
+fn high(cards: &[&Card]) -> bool {
+    ranks(cards).all(|rank| matches!(rank, 1 | 11..=13))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    fn classify(cards: &str) -> (&'static str, Vec<usize>) {
+        let situation = super::classify(&crate::strategy::cards(cards));
+        (situation.name, situation.held)
+    }
+
+    #[test]
+    fn classify_should_follow_the_chart_order() {
+        assert_eq!(
+            classify("♥A ♥K ♥Q ♥J ♥3"),
+            ("4 to a royal flush", vec![0, 1, 2, 3])
+        );
+        assert_eq!(
+            classify("♠9 ♥9 ♣9 ♦2 ♥5"),
+            ("Three of a kind", vec![0, 1, 2])
+        );
+        assert_eq!(classify("♠K ♥K ♣5 ♦5 ♥2"), ("Two pair", vec![0, 1, 2, 3]));
+        assert_eq!(
+            classify("♣4 ♣5 ♣6 ♣7 ♥7"),
+            ("4 to a straight flush", vec![0, 1, 2, 3])
+        );
+        assert_eq!(classify("♠3 ♥3 ♣J ♦8 ♥2"), ("Low pair", vec![0, 1]));
+        assert_eq!(classify("♠2 ♥J ♣Q ♦8 ♥4"), ("2 high cards", vec![1, 2]));
+        assert_eq!(classify("♠2 ♥3 ♣7 ♦8 ♥10"), ("Discard all", vec![]));
+    }
+
+    #[test]
+    fn classify_should_always_hold_jokers() {
+        assert_eq!(classify("★A ♥K ♣5 ♦8 ♥2"), ("High pair", vec![0, 1]));
+        assert_eq!(classify("★A ♣2 ♥7 ♠9 ♦4"), ("Low pair", vec![0, 1]));
+        assert_eq!(
+            classify("★A ★2 ♠Q ♥Q ♦Q"),
+            ("Five of a kind", vec![0, 1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn connected_should_accept_a_high_ace() {
+        assert_eq!(classify("♠A ♥K ♣Q ♦J ♥3").0, "4 to a straight");
+        assert_eq!(classify("♠A ♥2 ♣3 ♦4 ♥9").0, "4 to a straight");
+        assert_eq!(classify("♠A ♥2 ♣Q ♦K ♥9").0, "2 high cards");
+    }
+}
@@ -0,0 +1,185 @@
+use super::{classify, hold_ev};
+use crate::score_board::Rate;
+use crate::video_poker::{Player, Rule, VideoPoker};
+use alloc::vec::Vec;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub struct Comparison {
+    pub deals: usize,
+    pub baseline: Return,
+    pub other: Return,
+    pub situations: Vec<Loss>,
+}
+
+// Both per credit bet: `paid` is what the deals actually paid, `expected` the
+// exact EV of the holds, which is free of the luck of the draw.
+#[derive(Default)]
+pub struct Return {
+    pub paid: f64,
+    pub expected: f64,
+}
+
+pub struct Loss {
+    pub situation: &'static str,
+    pub deals: usize,
+    pub mistakes: usize,
+    // Summed over the deals where the holds differ in EV; negative when
+    // `other` holds better than the baseline.
+    pub ev_lost: f64,
+}
+
+impl Comparison {
+    pub fn rtp_difference(&self) -> f64 {
+        self.baseline.paid - self.other.paid
+    }
+
+    pub fn ev_difference(&self) -> f64 {
+        self.baseline.expected - self.other.expected
+    }
+}
+
+impl Loss {
+    // Frequency × EV lost, in credits per credit bet over every deal.
+    pub fn cost(&self, deals: usize) -> f64 {
+        self.ev_lost / deals.max(1) as f64
+    }
+}
+
+pub fn compare(
+    rule: Rule,
+    rate: &Rate,
+    baseline: &dyn Player,
+    other: &dyn Player,
+    deals: usize,
+    seed: u64,
+) -> Comparison {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut game = VideoPoker::new(rule);
+    let (mut baseline_return, mut other_return) = (Return::default(), Return::default());
+    let mut situations: Vec<Loss> = Vec::new();
+    for _ in 0..deals {
+        let seed = rng.next_u64();
+        let mut play = |player: &dyn Player, total: &mut Return| {
+            let dealt = game.deal(seed).to_vec();
+            let exchange = player.exchange(&dealt);
+            let held: Vec<usize> = (0..dealt.len()).filter(|i| !exchange.contains(i)).collect();
+            let hand = game.draw(1, exchange).unwrap().pop().flatten();
+            let ev = hold_ev(rule, rate, &dealt, &held);
+            total.paid += hand.map_or(0, |hand| rate.by_hand(&hand)) as f64;
+            total.expected += ev;
+            (dealt, ev)
+        };
+        let (dealt, baseline_ev) = play(baseline, &mut baseline_return);
+        let (_, other_ev) = play(other, &mut other_return);
+
+        let situation = classify(&dealt).name;
+        let index = match situations
+            .iter()
+            .position(|loss| loss.situation == situation)
+        {
+            Some(index) => index,
+            None => {
+                situations.push(Loss {
+                    situation,
+                    deals: 0,
+                    mistakes: 0,
+                    ev_lost: 0.0,
+                });
+                situations.len() - 1
+            }
+        };
+        let loss = &mut situations[index];
+        loss.deals += 1;
+        if (baseline_ev - other_ev).abs() > 1e-9 {
+            loss.mistakes += 1;
+            loss.ev_lost += baseline_ev - other_ev;
+        }
+    }
+    situations.sort_by(|a, b| b.ev_lost.total_cmp(&a.ev_lost));
+
+    let deals_f = deals.max(1) as f64;
+    for total in [&mut baseline_return, &mut other_return] {
+        total.paid /= deals_f;
+        total.expected /= deals_f;
+    }
+    Comparison {
+        deals,
+        baseline: baseline_return,
+        other: other_return,
+        situations,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::{ChartPlayer, NaivePlayer, PerfectPlayer};
+
+    #[test]
+    fn compare_should_find_no_loss_against_itself() {
+        let comparison = compare(
+            Rule::Jokers54Cards,
+            &Rate::JOKER_POKER,
+            &ChartPlayer,
+            &ChartPlayer,
+            50,
+            1,
+        );
+
+        assert_eq!(comparison.rtp_difference(), 0.0);
+        assert_eq!(comparison.ev_difference(), 0.0);
+        assert!(comparison.situations.iter().all(|loss| loss.mistakes == 0));
+        assert_eq!(
+            comparison
+                .situations
+                .iter()
+                .map(|loss| loss.deals)
+                .sum::<usize>(),
+            50
+        );
+    }
+
+    #[test]
+    fn compare_should_rank_the_situations_by_cost() {
+        let perfect = PerfectPlayer::new(Rule::Jokers54Cards, Rate::JOKER_POKER);
+        let comparison = compare(
+            Rule::Jokers54Cards,
+            &Rate::JOKER_POKER,
+            &perfect,
+            &NaivePlayer,
+            100,
+            2,
+        );
+        let costs: Vec<f64> = comparison
+            .situations
+            .iter()
+            .map(|loss| loss.cost(comparison.deals))
+            .collect();
+
+        assert!(comparison.ev_difference() > 0.0);
+        assert!(costs.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!((costs.iter().sum::<f64>() - comparison.ev_difference()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn compare_should_sum_the_costs_with_a_worse_baseline() {
+        let perfect = PerfectPlayer::new(Rule::Jokers54Cards, Rate::JOKER_POKER);
+        let comparison = compare(
+            Rule::Jokers54Cards,
+            &Rate::JOKER_POKER,
+            &NaivePlayer,
+            &perfect,
+            100,
+            2,
+        );
+        let costs: f64 = comparison
+            .situations
+            .iter()
+            .map(|loss| loss.cost(comparison.deals))
+            .sum();
+
+        assert!(comparison.ev_difference() < 0.0);
+        assert!((costs - comparison.ev_difference()).abs() < 1e-6);
+    }
+}
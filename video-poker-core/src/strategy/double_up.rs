@@ -0,0 +1,125 @@
+use crate::bonus_game::{Bonus, Choice, Guess, Hint, Outcome, Player};
+use crate::card::Card;
+use core::cell::Cell;
+
+pub struct AlwaysCollect;
+
+pub struct DoubleTimes {
+    times: usize,
+    best_guess: bool,
+    doubled: Cell<usize>,
+}
+
+impl DoubleTimes {
+    pub fn new(times: usize) -> Self {
+        DoubleTimes {
+            times,
+            best_guess: false,
+            doubled: Cell::new(0),
+        }
+    }
+
+    // Picks the guess with the best expected return instead of the first one offered.
+    pub fn with_best_guess(mut self) -> Self {
+        self.best_guess = true;
+        self
+    }
+}
+
+impl Player for AlwaysCollect {
+    fn new_round(&self, _bonus: &Bonus, _history: &[Card]) -> Choice {
+        Choice::Collect
+    }
+
+    fn guess(&self, _face_up: &[Card], hints: &[Hint]) -> Guess {
+        hints[0].guess.clone()
+    }
+
+    fn round_result(&self, _cards: &[Card], _guess: &Guess, _outcome: &Outcome) {}
+}
+
+impl Player for DoubleTimes {
+    fn new_round(&self, _bonus: &Bonus, _history: &[Card]) -> Choice {
+        if self.doubled.get() < self.times {
+            Choice::Double
+        } else {
+            self.doubled.set(0);
+            Choice::Collect
+        }
+    }
+
+    fn guess(&self, _face_up: &[Card], hints: &[Hint]) -> Guess {
+        let hint = if self.best_guess {
+            hints.iter().max_by(|a, b| a.ev.total_cmp(&b.ev)).unwrap()
+        } else {
+            &hints[0]
+        };
+        hint.guess.clone()
+    }
+
+    fn round_result(&self, _cards: &[Card], _guess: &Guess, outcome: &Outcome) {
+        // A push doubles nothing, so only wins count towards the doubles.
+        match outcome {
+            Outcome::Win => self.doubled.set(self.doubled.get() + 1),
+            Outcome::Push => {}
+            Outcome::Lose => self.doubled.set(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bonus_game::{BonusGame, DealerCard, Dealing, Gamble, HighLow};
+    use crate::money::Money;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn always_collect_should_keep_the_prize() {
+        let mut game = BonusGame::new(Dealing::Shoe, &mut ChaCha8Rng::seed_from_u64(1));
+
+        assert_eq!(
+            game.start(&HighLow, &mut AlwaysCollect, Money::new(100)),
+            Bonus::new(Money::new(100))
+        );
+        assert!(game.rounds().is_empty());
+    }
+
+    #[test]
+    fn double_times_should_stop_after_the_given_rounds() {
+        let mut game = BonusGame::new(Dealing::Shoe, &mut ChaCha8Rng::seed_from_u64(1));
+        let mut player = DoubleTimes::new(2).with_best_guess();
+        for _ in 0..50 {
+            let bonus = game.start(&DealerCard, &mut player, Money::new(100));
+            let wins = game
+                .rounds()
+                .iter()
+                .filter(|round| round.outcome == Outcome::Win)
+                .count();
+
+            assert!(wins <= 2);
+            assert!(bonus.is_lost() || wins == 2);
+        }
+    }
+
+    #[test]
+    fn double_times_should_not_count_a_push() {
+        let player = DoubleTimes::new(1);
+        let bonus = Bonus::new(Money::new(100));
+        player.round_result(&[], &Guess::Greater, &Outcome::Push);
+
+        assert!(player.new_round(&bonus, &[]) == Choice::Double);
+        player.round_result(&[], &Guess::Greater, &Outcome::Win);
+        assert!(player.new_round(&bonus, &[]) == Choice::Collect);
+    }
+
+    #[test]
+    fn best_guess_should_pick_the_highest_ev() {
+        let player = DoubleTimes::new(1).with_best_guess();
+        let hints = HighLow.hints(&[], &["♠3".parse().unwrap()]);
+
+        assert!(player.guess(&[], &hints) == Guess::Less);
+        assert!(DoubleTimes::new(1).guess(&[], &hints) == Guess::Greater);
+    }
+}
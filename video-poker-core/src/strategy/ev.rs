@@ -0,0 +1,268 @@
+use crate::card::{Card, Suit};
+use crate::score_board::Rate;
+use crate::video_poker::{Hand, Rule};
+use alloc::vec::Vec;
+
+const HAND_SIZE: usize = 5;
+const SUITS: [Suit; 4] = [Suit::Heart, Suit::Spade, Suit::Diamond, Suit::Club];
+
+pub fn hold_ev(rule: Rule, rate: &Rate, cards: &[Card], held: &[usize]) -> f64 {
+    let held: Vec<&Card> = held.iter().filter_map(|i| cards.get(*i)).collect();
+    Stub::new(rule, rate, cards).ev(&held)
+}
+
+pub fn best_hold(rule: Rule, rate: &Rate, cards: &[Card]) -> (Vec<usize>, f64) {
+    let stub = Stub::new(rule, rate, cards);
+    (0..1 << cards.len())
+        .map(|mask: usize| {
+            let held: Vec<usize> = (0..cards.len()).filter(|i| mask & 1 << i != 0).collect();
+            let ev = stub.ev(&held.iter().map(|i| &cards[*i]).collect::<Vec<_>>());
+            (held, ev)
+        })
+        .fold((Vec::new(), f64::MIN), |best, hold| {
+            if hold.1 > best.1 { hold } else { best }
+        })
+}
+
+// The cards left in the deck once a hand is dealt. Draws are summed by rank
+// counts, as if no hand could be a flush, and then the few draws that do make
+// a flush are enumerated to correct the sum.
+struct Stub<'a> {
+    rate: &'a Rate,
+    cards: Vec<Card>,
+    ranks: [usize; 13],
+    jokers: usize,
+}
+
+impl<'a> Stub<'a> {
+    fn new(rule: Rule, rate: &'a Rate, dealt: &[Card]) -> Self {
+        let cards: Vec<Card> = rule
+            .create_deck()
+            .cards()
+            .iter()
+            .filter(|card| !dealt.contains(card))
+            .cloned()
+            .collect();
+        let (ranks, jokers) = count(&cards.iter().collect::<Vec<_>>());
+        Stub {
+            rate,
+            cards,
+            ranks,
+            jokers,
+        }
+    }
+
+    fn ev(&self, held: &[&Card]) -> f64 {
+        let draws = HAND_SIZE.saturating_sub(held.len());
+        let (mut entries, jokers) = count(held);
+        let mut sum = self.sum_ranks(0, draws, &mut entries, jokers);
+
+        let mut suits = held
+            .iter()
+            .filter(|card| card.suit != Suit::Joker)
+            .map(|card| &card.suit);
+        let flush_suits: &[Suit] = match suits.next() {
+            Some(first) if suits.all(|suit| suit == first) => core::slice::from_ref(first),
+            Some(_) => &[],
+            None => &SUITS,
+        };
+        for suit in flush_suits {
+            let suited: Vec<&Card> = self
+                .cards
+                .iter()
+                .filter(|card| card.suit == *suit || card.suit == Suit::Joker)
+                .collect();
+            combinations(&suited, draws, &mut Vec::new(), &mut |drawn| {
+                let (mut entries, jokers) = count(held);
+                let (drawn_entries, drawn_jokers) = count(drawn);
+                entries
+                    .iter_mut()
+                    .zip(drawn_entries)
+                    .for_each(|(entry, drawn)| *entry += drawn);
+                let jokers = jokers + drawn_jokers;
+                sum += self.pay(Hand::from_ranks(&entries, jokers, true))
+                    - self.pay(Hand::from_ranks(&entries, jokers, false));
+            });
+        }
+        sum / binomial(self.cards.len(), draws)
+    }
+
+    fn sum_ranks(
+        &self,
+        rank: usize,
+        draws: usize,
+        entries: &mut [usize; 13],
+        jokers: usize,
+    ) -> f64 {
+        if rank == entries.len() {
+            return binomial(self.jokers, draws)
+                * self.pay(Hand::from_ranks(entries, jokers + draws, false));
+        }
+        (0..=draws.min(self.ranks[rank]))
+            .map(|count| {
+                entries[rank] += count;
+                let sum = binomial(self.ranks[rank], count)
+                    * self.sum_ranks(rank + 1, draws - count, entries, jokers);
+                entries[rank] -= count;
+                sum
+            })
+            .sum()
+    }
+
+    fn pay(&self, hand: Option<Hand>) -> f64 {
+        hand.map_or(0, |hand| self.rate.by_hand(&hand)) as f64
+    }
+}
+
+fn count(cards: &[&Card]) -> ([usize; 13], usize) {
+    cards
+        .iter()
+        .fold(([0; 13], 0), |(mut entries, jokers), card| {
+            if card.suit == Suit::Joker {
+                (entries, jokers + 1)
+            } else {
+                entries[(card.rank.value() - 1) as usize] += 1;
+                (entries, jokers)
+            }
+        })
+}
+
+fn combinations<'a>(
+    cards: &[&'a Card],
+    count: usize,
+    chosen: &mut Vec<&'a Card>,
+    visit: &mut impl FnMut(&[&'a Card]),
+) {
+    if count == 0 {
+        visit(chosen);
+        return;
+    }
+    for i in 0..cards.len() {
+        chosen.push(cards[i]);
+        combinations(&cards[i + 1..], count - 1, chosen, visit);
+        chosen.pop();
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (0..k).fold(1.0, |result, i| result * (n - i) as f64 / (i + 1) as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::cards;
+    use crate::video_poker::VideoPoker;
+    use alloc::vec;
+
+    fn brute_force(rule: Rule, cards: &[Card], held: &[usize]) -> f64 {
+        let stub = Stub::new(rule, &Rate::JOKER_POKER, cards);
+        let held: Vec<&Card> = held.iter().map(|i| &cards[*i]).collect();
+        let (mut sum, mut deals) = (0.0, 0.0);
+        combinations(
+            &stub.cards.iter().collect::<Vec<_>>(),
+            HAND_SIZE - held.len(),
+            &mut Vec::new(),
+            &mut |drawn| {
+                let hand: Vec<Card> = held
+                    .iter()
+                    .chain(drawn)
+                    .map(|card| (*card).clone())
+                    .collect();
+                sum += stub.pay(Hand::from_cards(&hand));
+                deals += 1.0;
+            },
+        );
+        sum / deals
+    }
+
+    fn assert_matches_brute_force(rule: Rule, cards: &[Card], min_held: usize) {
+        for mask in 0_usize..32 {
+            let held: Vec<usize> = (0..5).filter(|i| mask & 1 << i != 0).collect();
+            if held.len() >= min_held {
+                let ev = hold_ev(rule, &Rate::JOKER_POKER, cards, &held);
+                let expected = brute_force(rule, cards, &held);
+                assert!(
+                    (ev - expected).abs() < 1e-9,
+                    "{:?}: {} != {}",
+                    held,
+                    ev,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hold_ev_should_match_brute_force() {
+        assert_matches_brute_force(Rule::Default52Cards, &cards("♥A ♥K ♥Q ♠5 ♣5"), 1);
+        assert_matches_brute_force(Rule::Jokers54Cards, &cards("★A ♦J ♦10 ♦4 ♣9"), 2);
+    }
+
+    #[test]
+    fn hold_ev_should_match_the_draws_of_the_game() {
+        let mut game = VideoPoker::new(Rule::Jokers54Cards);
+        for seed in 0..10 {
+            let dealt = game.deal(seed).to_vec();
+            let held = [0, 1];
+            let ev = hold_ev(Rule::Jokers54Cards, &Rate::JOKER_POKER, &dealt, &held);
+            let paid: Vec<f64> = game
+                .draw(20_000, vec![2, 3, 4])
+                .unwrap()
+                .into_iter()
+                .map(|hand| hand.map_or(0, |hand| Rate::JOKER_POKER.by_hand(&hand)) as f64)
+                .collect();
+            let n = paid.len() as f64;
+            let mean = paid.iter().sum::<f64>() / n;
+            let variance = paid
+                .iter()
+                .map(|pay| (pay - mean) * (pay - mean))
+                .sum::<f64>()
+                / n;
+
+            // Within four standard errors.
+            assert!(
+                (mean - ev) * (mean - ev) < 16.0 * variance / n,
+                "seed {}: {} != {}",
+                seed,
+                mean,
+                ev
+            );
+        }
+    }
+
+    #[test]
+    fn hold_ev_should_pay_a_pat_hand_exactly() {
+        let cards = cards("♠A ♠K ♠Q ♠J ♠10");
+
+        assert_eq!(
+            hold_ev(
+                Rule::Default52Cards,
+                &Rate::JOKER_POKER,
+                &cards,
+                &[0, 1, 2, 3, 4]
+            ),
+            500.0
+        );
+    }
+
+    #[test]
+    fn best_hold_should_keep_four_to_a_royal_over_a_flush() {
+        let cards = cards("♥A ♥K ♥Q ♥J ♥3");
+        let (held, ev) = best_hold(Rule::Default52Cards, &Rate::JOKER_POKER, &cards);
+
+        assert_eq!(held, vec![0, 1, 2, 3]);
+        assert!(ev > 7.0);
+    }
+
+    #[test]
+    fn best_hold_should_keep_the_jokers() {
+        let cards = cards("★A ♣2 ♥7 ♠9 ♦4");
+        let (held, _) = best_hold(Rule::Jokers54Cards, &Rate::JOKER_POKER, &cards);
+
+        assert!(held.contains(&0));
+    }
+}
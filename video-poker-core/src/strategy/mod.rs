@@ -0,0 +1,16 @@
+mod chart;
+mod compare;
+mod double_up;
+mod ev;
+mod player;
+
+pub use chart::{Situation, classify};
+pub use compare::{Comparison, Loss, Return, compare};
+pub use double_up::{AlwaysCollect, DoubleTimes};
+pub use ev::{best_hold, hold_ev};
+pub use player::{ChartPlayer, NaivePlayer, PerfectPlayer, RandomPlayer};
+
+#[cfg(test)]
+fn cards(cards: &str) -> alloc::vec::Vec<crate::card::Card> {
+    cards.split(' ').map(|card| card.parse().unwrap()).collect()
+}
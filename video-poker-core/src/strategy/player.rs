@@ -0,0 +1,140 @@
+use super::{best_hold, classify};
+use crate::card::{Card, Suit};
+use crate::score_board::Rate;
+use crate::video_poker::{Player, Rule};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub struct RandomPlayer {
+    rng: RefCell<ChaCha8Rng>,
+}
+
+pub struct NaivePlayer;
+
+pub struct ChartPlayer;
+
+pub struct PerfectPlayer {
+    rule: Rule,
+    rate: Rate,
+}
+
+impl RandomPlayer {
+    pub fn new(seed: u64) -> Self {
+        RandomPlayer {
+            rng: RefCell::new(ChaCha8Rng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl PerfectPlayer {
+    pub fn new(rule: Rule, rate: Rate) -> Self {
+        PerfectPlayer { rule, rate }
+    }
+}
+
+impl Player for RandomPlayer {
+    fn show_cards(&self, _cards: &[Card]) {}
+
+    fn exchange(&self, cards: &[Card]) -> Vec<usize> {
+        let mut rng = self.rng.borrow_mut();
+        (0..cards.len()).filter(|_| rng.random_bool(0.5)).collect()
+    }
+}
+
+// Holds jokers, anything paired and every jack or better.
+impl Player for NaivePlayer {
+    fn show_cards(&self, _cards: &[Card]) {}
+
+    fn exchange(&self, cards: &[Card]) -> Vec<usize> {
+        let held = |card: &Card| {
+            card.suit == Suit::Joker
+                || matches!(card.rank.value(), 1 | 11..=13)
+                || cards
+                    .iter()
+                    .filter(|other| other.suit != Suit::Joker && other.rank == card.rank)
+                    .count()
+                    > 1
+        };
+        (0..cards.len()).filter(|i| !held(&cards[*i])).collect()
+    }
+}
+
+impl Player for ChartPlayer {
+    fn show_cards(&self, _cards: &[Card]) {}
+
+    fn exchange(&self, cards: &[Card]) -> Vec<usize> {
+        let held = classify(cards).held;
+        (0..cards.len()).filter(|i| !held.contains(i)).collect()
+    }
+}
+
+impl Player for PerfectPlayer {
+    fn show_cards(&self, _cards: &[Card]) {}
+
+    fn exchange(&self, cards: &[Card]) -> Vec<usize> {
+        let (held, _) = best_hold(self.rule, &self.rate, cards);
+        (0..cards.len()).filter(|i| !held.contains(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::cards;
+    use crate::strategy::hold_ev;
+    use crate::video_poker::{Hand, VideoPoker};
+    use alloc::vec;
+
+    #[test]
+    fn naive_player_should_hold_pairs_and_high_cards() {
+        assert_eq!(NaivePlayer.exchange(&cards("♠3 ♥3 ♣J ♦8 ★A")), vec![3]);
+        assert_eq!(
+            NaivePlayer.exchange(&cards("♠2 ♥4 ♣6 ♦8 ♥10")),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn perfect_player_should_break_a_flush_for_four_to_a_royal() {
+        let player = PerfectPlayer::new(Rule::Default52Cards, Rate::JOKER_POKER);
+
+        assert_eq!(player.exchange(&cards("♥A ♥K ♥Q ♥J ♥3")), vec![4]);
+    }
+
+    #[test]
+    fn bots_should_play_full_rounds() {
+        fn play(game: &mut VideoPoker, player: &mut impl Player, seed: u64) -> f64 {
+            let hand = game.start_seeded(player, 1, seed).pop().flatten();
+            assert_eq!(hand, Hand::from_cards(game.cards()));
+            let round = game.round();
+            assert!(
+                round
+                    .held
+                    .iter()
+                    .all(|i| round.dealt[*i] == game.cards()[*i])
+            );
+            hold_ev(
+                Rule::Jokers54Cards,
+                &Rate::JOKER_POKER,
+                &round.dealt,
+                &round.held,
+            )
+        }
+
+        let mut game = VideoPoker::new(Rule::Jokers54Cards);
+        let mut random = RandomPlayer::new(7);
+        let mut perfect = PerfectPlayer::new(Rule::Jokers54Cards, Rate::JOKER_POKER);
+        for seed in 0..20 {
+            let best = play(&mut game, &mut perfect, seed);
+            for ev in [
+                play(&mut game, &mut random, seed),
+                play(&mut game, &mut NaivePlayer, seed),
+                play(&mut game, &mut ChartPlayer, seed),
+            ] {
+                assert!(ev <= best + 1e-9);
+            }
+        }
+    }
+}
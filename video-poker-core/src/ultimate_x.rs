@@ -0,0 +1,89 @@
+use crate::money::Money;
+use crate::score_board::Rate;
+use crate::video_poker::Hand;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub struct UltimateX {
+    table: Rate,
+    multipliers: Vec<usize>,
+}
+
+impl UltimateX {
+    pub fn new(table: Rate, hands: usize) -> Self {
+        UltimateX {
+            table,
+            multipliers: vec![1; hands],
+        }
+    }
+
+    pub fn hands(&self) -> usize {
+        self.multipliers.len()
+    }
+
+    pub fn multipliers(&self) -> &[usize] {
+        &self.multipliers
+    }
+
+    pub fn wager(&self, bet: Money) -> Option<Money> {
+        UltimateX::wager_of(self.hands(), bet)
+    }
+
+    // Every hand is played at twice the bet. `None` when it overflows.
+    pub fn wager_of(hands: usize, bet: Money) -> Option<Money> {
+        bet.checked_mul(hands)?.checked_mul(2)
+    }
+
+    pub fn advance(&mut self, hands: &[Option<Hand>]) {
+        self.multipliers
+            .iter_mut()
+            .zip(hands)
+            .for_each(|(multiplier, hand)| {
+                *multiplier = hand.as_ref().map(|h| self.table.by_hand(h)).unwrap_or(1);
+            });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TABLE: Rate = Rate {
+        royal_flush: 4,
+        five_of_a_kind: 4,
+        straight_flush: 4,
+        four_of_a_kind: 3,
+        full_house: 12,
+        flush: 11,
+        straight: 7,
+        three_of_a_kind: 4,
+        two_pair: 3,
+        jacks_or_better: 2,
+    };
+
+    #[test]
+    fn new_should_start_every_hand_at_1x() {
+        let ultimate_x = UltimateX::new(TABLE, 3);
+
+        assert_eq!(ultimate_x.multipliers(), &[1, 1, 1]);
+    }
+
+    #[test]
+    fn wager_should_double_the_bet_of_every_hand() {
+        let ultimate_x = UltimateX::new(TABLE, 3);
+
+        assert_eq!(ultimate_x.wager(Money::new(50)), Some(Money::new(300)));
+        assert_eq!(ultimate_x.wager(Money::new(usize::MAX / 4)), None);
+    }
+
+    #[test]
+    fn advance_should_look_up_multipliers_for_next_round() {
+        let mut ultimate_x = UltimateX::new(TABLE, 3);
+        ultimate_x.advance(&[Some(Hand::FullHouse), None, Some(Hand::TwoPair)]);
+
+        assert_eq!(ultimate_x.multipliers(), &[12, 1, 3]);
+
+        ultimate_x.advance(&[None, None, None]);
+        assert_eq!(ultimate_x.multipliers(), &[1, 1, 1]);
+    }
+}
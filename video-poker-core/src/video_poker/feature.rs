@@ -0,0 +1,189 @@
+use super::hand::Hand;
+use crate::card::Card;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use rand::{Rng, RngCore};
+
+// Send so a game with features can be played across await points.
+pub trait Feature: Send {
+    fn on_deal(&mut self, _cards: &[Card], _rng: &mut dyn RngCore) {}
+
+    fn on_draw(&mut self, _hands: &[Option<Hand>]) {}
+
+    // `hand` is the position of the paying hand among the hands drawn.
+    fn on_payout(&mut self, _hand: usize, prize: usize) -> usize {
+        prize
+    }
+
+    fn status(&self) -> Option<String> {
+        None
+    }
+}
+
+pub struct SuperTimesPay {
+    probability: f64,
+    multiplier: Option<usize>,
+}
+
+impl SuperTimesPay {
+    const MULTIPLIERS: core::ops::RangeInclusive<usize> = 2..=10;
+
+    pub fn new(probability: f64) -> Self {
+        SuperTimesPay {
+            probability: probability.clamp(0.0, 1.0),
+            multiplier: None,
+        }
+    }
+}
+
+impl Feature for SuperTimesPay {
+    fn on_deal(&mut self, _cards: &[Card], rng: &mut dyn RngCore) {
+        self.multiplier = (rng.random_range(0.0..1.0) < self.probability)
+            .then(|| rng.random_range(SuperTimesPay::MULTIPLIERS));
+    }
+
+    fn on_payout(&mut self, _hand: usize, prize: usize) -> usize {
+        prize.saturating_mul(self.multiplier.unwrap_or(1))
+    }
+
+    fn status(&self) -> Option<String> {
+        self.multiplier
+            .map(|multiplier| format!("Super Times Pay x{}", multiplier))
+    }
+}
+
+// The hands of a game climb one level after every win. A win unlocks the
+// next hand, so every hand after a loss is locked and pays nothing. Every
+// game starts again from the first level.
+pub struct MultiStrike {
+    multipliers: Vec<usize>,
+    // The level of every hand, `None` once it is locked.
+    levels: Vec<Option<usize>>,
+}
+
+impl MultiStrike {
+    pub fn new(multipliers: Vec<usize>) -> Self {
+        MultiStrike {
+            multipliers,
+            levels: Vec::new(),
+        }
+    }
+
+    fn multiplier(&self, level: usize) -> usize {
+        self.multipliers.get(level).copied().unwrap_or(1)
+    }
+}
+
+impl Feature for MultiStrike {
+    fn on_deal(&mut self, _cards: &[Card], _rng: &mut dyn RngCore) {
+        self.levels.clear();
+    }
+
+    fn on_draw(&mut self, hands: &[Option<Hand>]) {
+        let top = self.multipliers.len().saturating_sub(1);
+        let mut level = Some(0);
+        self.levels = hands
+            .iter()
+            .map(|hand| {
+                let applied = level;
+                level = level
+                    .filter(|_| hand.is_some())
+                    .map(|level| (level + 1).min(top));
+                applied
+            })
+            .collect();
+    }
+
+    fn on_payout(&mut self, hand: usize, prize: usize) -> usize {
+        match self.levels.get(hand) {
+            Some(None) => 0,
+            Some(Some(level)) => prize.saturating_mul(self.multiplier(*level)),
+            None => prize,
+        }
+    }
+
+    fn status(&self) -> Option<String> {
+        self.levels.iter().flatten().max().map(|level| {
+            format!(
+                "Multi-Strike level {} (x{})",
+                level + 1,
+                self.multiplier(*level)
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn super_times_pay_should_multiply_prize_when_triggered() {
+        let mut feature = SuperTimesPay::new(1.0);
+        feature.on_deal(&[], &mut ChaCha8Rng::seed_from_u64(1));
+        let prize = feature.on_payout(0, 10);
+
+        assert!(prize.is_multiple_of(10) && SuperTimesPay::MULTIPLIERS.contains(&(prize / 10)));
+        assert!(feature.status().is_some());
+    }
+
+    #[test]
+    fn super_times_pay_should_keep_prize_when_not_triggered() {
+        let mut feature = SuperTimesPay::new(0.0);
+        feature.on_deal(&[], &mut ChaCha8Rng::seed_from_u64(1));
+
+        assert_eq!(feature.on_payout(0, 10), 10);
+        assert!(feature.status().is_none());
+    }
+
+    #[test]
+    fn multi_strike_should_climb_across_the_hands_of_a_game() {
+        let mut feature = MultiStrike::new(vec![1, 2, 4, 8]);
+        feature.on_draw(&[Some(Hand::JacksOrBetter); 5]);
+        let prizes: Vec<usize> = (0..5).map(|hand| feature.on_payout(hand, 10)).collect();
+
+        assert_eq!(prizes, vec![10, 20, 40, 80, 80]);
+        assert_eq!(feature.status().unwrap(), "Multi-Strike level 4 (x8)");
+    }
+
+    #[test]
+    fn multi_strike_should_lock_the_hands_after_a_loss() {
+        let mut feature = MultiStrike::new(vec![1, 2, 4, 8]);
+        feature.on_draw(&[Some(Hand::JacksOrBetter), None, Some(Hand::JacksOrBetter)]);
+
+        assert_eq!(feature.on_payout(0, 10), 10);
+        assert_eq!(feature.on_payout(2, 10), 0);
+        assert_eq!(feature.status().unwrap(), "Multi-Strike level 2 (x2)");
+    }
+
+    #[test]
+    fn multi_strike_should_pay_nothing_after_a_loss_on_the_first_hand() {
+        let mut feature = MultiStrike::new(vec![1, 2, 4, 8]);
+        feature.on_draw(&[
+            None,
+            Some(Hand::FullHouse),
+            Some(Hand::Flush),
+            Some(Hand::TwoPair),
+        ]);
+        let prizes: Vec<usize> = (1..4).map(|hand| feature.on_payout(hand, 10)).collect();
+
+        assert_eq!(prizes, vec![0, 0, 0]);
+        assert_eq!(feature.status().unwrap(), "Multi-Strike level 1 (x1)");
+    }
+
+    #[test]
+    fn multi_strike_should_start_every_game_from_the_first_level() {
+        let mut feature = MultiStrike::new(vec![1, 2, 4, 8]);
+        feature.on_draw(&[Some(Hand::JacksOrBetter); 3]);
+        feature.on_deal(&[], &mut ChaCha8Rng::seed_from_u64(1));
+
+        assert!(feature.status().is_none());
+        feature.on_draw(&[Some(Hand::JacksOrBetter)]);
+        assert_eq!(feature.on_payout(0, 10), 10);
+        assert_eq!(feature.status().unwrap(), "Multi-Strike level 1 (x1)");
+    }
+}
@@ -1,46 +1,218 @@
+use super::feature::Feature;
 use super::hand::Hand;
-use super::player::Player;
+use super::player::{AsyncPlayer, Awaiting, Blocking, Player, Turns};
 use crate::card::Card;
 use crate::deck::Deck;
+use crate::ready;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 pub struct VideoPoker {
+    rule: Rule,
     deck: Deck,
+    round: Round,
+    rng: Option<ChaCha8Rng>,
+    features: Vec<Box<dyn Feature>>,
 }
 
 impl VideoPoker {
     pub fn new(rule: Rule) -> Self {
         VideoPoker {
             deck: rule.create_deck(),
+            rule,
+            round: Round::default(),
+            rng: None,
+            features: Vec::new(),
         }
     }
 
-    pub fn start(&mut self, player: &mut impl Player) -> Option<Hand> {
-        self.deck.shuffle();
-        let mut players_deck = self.create_deck();
-        player.show_cards(&players_deck);
+    pub fn with_feature(mut self, feature: impl Feature + 'static) -> Self {
+        self.features.push(Box::new(feature));
+        self
+    }
+
+    pub fn feature_status(&self) -> Vec<String> {
+        self.features
+            .iter()
+            .filter_map(|feature| feature.status())
+            .collect()
+    }
+
+    pub(crate) fn payout(&mut self, hand: usize, prize: usize) -> usize {
+        self.features
+            .iter_mut()
+            .fold(prize, |prize, feature| feature.on_payout(hand, prize))
+    }
+
+    pub fn round(&self) -> &Round {
+        &self.round
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        self.round.hands.first().map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn hands(&self) -> &[Vec<Card>] {
+        &self.round.hands
+    }
+
+    pub fn start(&mut self, player: &mut impl Player, rng: &mut impl RngCore) -> Option<Hand> {
+        self.start_multi(player, 1, rng).pop().flatten()
+    }
+
+    pub fn start_multi(
+        &mut self,
+        player: &mut impl Player,
+        hands: usize,
+        rng: &mut impl RngCore,
+    ) -> Vec<Option<Hand>> {
+        self.start_seeded(player, hands, rng.next_u64())
+    }
+
+    pub fn start_seeded(
+        &mut self,
+        player: &mut impl Player,
+        hands: usize,
+        seed: impl Into<Seed>,
+    ) -> Vec<Option<Hand>> {
+        ready::now(self.play(&Blocking(player), hands, seed.into()))
+    }
+
+    pub async fn start_async(
+        &mut self,
+        player: &mut impl AsyncPlayer,
+        rng: &mut (impl RngCore + Send),
+    ) -> Option<Hand> {
+        self.start_multi_async(player, 1, rng).await.pop().flatten()
+    }
+
+    pub async fn start_multi_async(
+        &mut self,
+        player: &mut impl AsyncPlayer,
+        hands: usize,
+        rng: &mut (impl RngCore + Send),
+    ) -> Vec<Option<Hand>> {
+        let seed = Seed::from(rng.next_u64());
+        self.start_seeded_async(player, hands, seed).await
+    }
+
+    pub async fn start_seeded_async(
+        &mut self,
+        player: &mut impl AsyncPlayer,
+        hands: usize,
+        seed: Seed,
+    ) -> Vec<Option<Hand>> {
+        self.play(&Awaiting(player), hands, seed).await
+    }
+
+    async fn play(&mut self, turns: &impl Turns, hands: usize, seed: Seed) -> Vec<Option<Hand>> {
+        let rng = self.shuffle(seed);
+        let dealt = self.round.dealt.clone();
+        turns.show_cards(&dealt).await;
+        let exchange = turns.exchange(&dealt).await;
+        let results = self.exchange(rng, hands, exchange);
+        turns.show_hands(&self.round.hands).await;
+        results
+    }
+
+    pub fn is_dealt(&self) -> bool {
+        self.rng.is_some()
+    }
+
+    pub fn deal(&mut self, seed: impl Into<Seed>) -> &[Card] {
+        self.rng = Some(self.shuffle(seed.into()));
+        &self.round.dealt
+    }
+
+    pub fn draw(
+        &mut self,
+        hands: usize,
+        indice_to_exchange: Vec<usize>,
+    ) -> Result<Vec<Option<Hand>>, &'static str> {
+        let rng = self.rng.take().ok_or("No cards dealt")?;
+        Ok(self.exchange(rng, hands, indice_to_exchange))
+    }
 
-        let indice_to_exchange = player.exchange(&players_deck);
-        self.exchange_cards(&mut players_deck, indice_to_exchange);
-        player.show_cards(&players_deck);
+    fn shuffle(&mut self, seed: Seed) -> ChaCha8Rng {
+        let mut rng = seed.rng();
+        // Features draw from their own stream, so the cards don't depend on
+        // which features are installed and a bare game can verify the deal.
+        let mut features_rng = rng.clone();
+        features_rng.set_stream(1);
+        self.deck = self.rule.create_deck();
+        self.deck.shuffle(&mut rng);
+        let players_deck = self.create_deck();
+        self.features
+            .iter_mut()
+            .for_each(|feature| feature.on_deal(&players_deck, &mut features_rng));
+        self.round = Round {
+            seed,
+            dealt: players_deck,
+            ..Round::default()
+        };
+        rng
+    }
 
-        let hand = Hand::from_cards(&players_deck);
+    fn exchange(
+        &mut self,
+        mut rng: ChaCha8Rng,
+        hands: usize,
+        mut indice_to_exchange: Vec<usize>,
+    ) -> Vec<Option<Hand>> {
+        let mut players_deck = self.round.dealt.clone();
+        indice_to_exchange.sort();
+        indice_to_exchange.dedup();
+        indice_to_exchange.retain(|i| *i < players_deck.len());
+        let discards: Vec<Card> = indice_to_exchange
+            .iter()
+            .rev()
+            .map(|i| players_deck.remove(*i))
+            .collect();
+        self.round.held = (0..self.round.dealt.len())
+            .filter(|i| !indice_to_exchange.contains(i))
+            .collect();
+        self.round.hands = (0..hands)
+            .map(|_| self.exchange_cards(&mut rng, &players_deck, &indice_to_exchange))
+            .collect();
+
+        self.push_back_to_deck(discards);
         self.push_back_to_deck(players_deck);
-        hand
+        let hands: Vec<Option<Hand>> = self
+            .round
+            .hands
+            .iter()
+            .map(|cards| Hand::from_cards(cards))
+            .collect();
+        self.features
+            .iter_mut()
+            .for_each(|feature| feature.on_draw(&hands));
+        hands
     }
 
     fn create_deck(&mut self) -> Vec<Card> {
         (0..5).map(|_| self.deck.draw().unwrap()).collect()
     }
 
-    fn exchange_cards(&mut self, deck: &mut Vec<Card>, indice_to_exchange: Vec<usize>) {
+    fn exchange_cards(
+        &mut self,
+        rng: &mut ChaCha8Rng,
+        held: &[Card],
+        indice_to_exchange: &[usize],
+    ) -> Vec<Card> {
+        self.deck.shuffle(rng);
+        let mut deck = held.to_vec();
         indice_to_exchange
             .iter()
-            .rev()
-            .for_each(|i| self.deck.push(deck.remove(*i)));
-        self.deck.shuffle();
+            .for_each(|i| deck.insert(*i, self.deck.draw().unwrap()));
         indice_to_exchange
-            .into_iter()
-            .for_each(|i| deck.insert(i, self.deck.draw().unwrap()));
+            .iter()
+            .for_each(|i| self.deck.push(deck[*i].clone()));
+        deck
     }
 
     fn push_back_to_deck(&mut self, players_deck: Vec<Card>) {
@@ -50,16 +222,178 @@ impl VideoPoker {
     }
 }
 
+#[derive(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Round {
+    pub seed: Seed,
+    pub dealt: Vec<Card>,
+    pub held: Vec<usize>,
+    pub hands: Vec<Vec<Card>>,
+}
+
+// The full 32-byte ChaCha8 key a round is dealt from. A u64 seed expands the
+// same way `SeedableRng::seed_from_u64` does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Seed([u8; 32]);
+
+impl Seed {
+    pub fn bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    pub(crate) fn rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::from_seed(self.0)
+    }
+}
+
+impl From<[u8; 32]> for Seed {
+    fn from(bytes: [u8; 32]) -> Self {
+        Seed(bytes)
+    }
+}
+
+impl From<u64> for Seed {
+    fn from(seed: u64) -> Self {
+        Seed(ChaCha8Rng::seed_from_u64(seed).get_seed())
+    }
+}
+
+impl Display for Seed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl FromStr for Seed {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 64 || !s.is_ascii() {
+            return Err("Invalid seed");
+        }
+        let mut bytes = [0; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).map_err(|_| "Invalid seed")?;
+        }
+        Ok(Seed(bytes))
+    }
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rule {
     Default52Cards,
     Jokers54Cards,
 }
 
 impl Rule {
-    fn create_deck(&self) -> Deck {
+    pub(crate) fn create_deck(&self) -> Deck {
         match self {
             Self::Default52Cards => Deck::default_52_cards(),
             Self::Jokers54Cards => Deck::joker_54_cards(),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::video_poker::SuperTimesPay;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use futures::executor::block_on;
+    use proptest::prelude::*;
+
+    struct Exchange(Vec<usize>);
+
+    impl Player for Exchange {
+        fn show_cards(&self, _cards: &[Card]) {}
+
+        fn exchange(&self, _cards: &[Card]) -> Vec<usize> {
+            self.0.clone()
+        }
+    }
+
+    impl AsyncPlayer for Exchange {
+        async fn show_cards(&self, _cards: &[Card]) {}
+
+        async fn exchange(&self, _cards: &[Card]) -> Vec<usize> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn deal_and_draw_should_match_start_seeded() {
+        let mut game = VideoPoker::new(Rule::Jokers54Cards);
+        game.start_seeded(&mut Exchange(vec![0, 3]), 2, 42);
+        let round = game.round().clone();
+
+        let mut stepped = VideoPoker::new(Rule::Jokers54Cards);
+        assert_eq!(stepped.deal(42), &round.dealt[..]);
+        assert!(stepped.is_dealt());
+        stepped.draw(2, vec![0, 3]).unwrap();
+        assert!(stepped.round() == &round);
+        assert_eq!(stepped.draw(2, vec![0, 3]), Err("No cards dealt"));
+    }
+
+    #[test]
+    fn start_async_should_play_a_round() {
+        let mut game = VideoPoker::new(Rule::Jokers54Cards).with_feature(SuperTimesPay::new(0.5));
+        let mut player = Exchange(Vec::new());
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let future = game.start_async(&mut player, &mut rng);
+        let _: &dyn Send = &future;
+        let hand = block_on(future);
+
+        let round = game.round();
+        assert_eq!(round.hands, vec![round.dealt.clone()]);
+        assert_eq!(hand, Hand::from_cards(&round.dealt));
+        assert!(!game.is_dealt());
+    }
+
+    #[test]
+    fn seed_should_parse_what_it_prints() {
+        let seed = Seed::from(42);
+
+        assert_eq!(seed.to_string().parse(), Ok(seed));
+        assert_eq!("42".parse::<Seed>(), Err("Invalid seed"));
+        assert!(seed.rng() == ChaCha8Rng::seed_from_u64(42));
+    }
+
+    #[test]
+    fn start_seeded_async_should_deal_like_start_seeded() {
+        let mut game = VideoPoker::new(Rule::Jokers54Cards);
+        let hands = game.start_seeded(&mut Exchange(vec![1, 4]), 3, 9);
+        let round = game.round().clone();
+
+        let mut stepped = VideoPoker::new(Rule::Jokers54Cards);
+        let mut player = Exchange(vec![1, 4]);
+        let future = stepped.start_seeded_async(&mut player, 3, Seed::from(9));
+        let _: &dyn Send = &future;
+
+        assert_eq!(block_on(future), hands);
+        assert!(stepped.round() == &round);
+    }
+
+    proptest! {
+        #[test]
+        fn start_seeded_should_accept_any_exchange(
+            seed: u64,
+            hands in 1_usize..5,
+            indices in proptest::collection::vec(0_usize..8, 0..8),
+        ) {
+            let mut game = VideoPoker::new(Rule::Jokers54Cards);
+            let results = game.start_seeded(&mut Exchange(indices), hands, seed);
+            let round = game.round();
+
+            prop_assert_eq!(results.len(), hands);
+            prop_assert_eq!(game.deck.len(), 54);
+            for cards in &round.hands {
+                prop_assert_eq!(cards.len(), 5);
+                prop_assert!(round.held.iter().all(|i| cards[*i] == round.dealt[*i]));
+                prop_assert!(cards.iter().enumerate().all(|(i, card)| !cards[..i].contains(card)));
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use crate::card::{Card, Suit};
-use std::fmt::{Display, Formatter, Result};
+use alloc::string::ToString;
+use core::fmt::{Display, Formatter, Result};
+use core::str::FromStr;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Hand {
     RoyalFlush,
     StraightFlush,
@@ -16,13 +19,31 @@ pub enum Hand {
 }
 
 impl Hand {
+    pub const ALL: [Hand; 10] = [
+        Hand::RoyalFlush,
+        Hand::StraightFlush,
+        Hand::FiveOfAKind,
+        Hand::FourOfAKind,
+        Hand::FullHouse,
+        Hand::Flush,
+        Hand::Straight,
+        Hand::ThreeOfAKind,
+        Hand::TwoPair,
+        Hand::JacksOrBetter,
+    ];
+
     pub fn from_cards(cards: &[Card]) -> Option<Self> {
-        let is_royal_straight = Hand::is_royal_straight(cards);
-        let is_straight = Hand::is_straight(cards);
-        let is_flush = Hand::is_flush(cards);
-        let same_ranks = Hand::same_ranks(cards);
-        let jokers = Hand::jokers(cards);
-        let entries = Hand::count(cards);
+        Hand::from_ranks(
+            &Hand::count(cards),
+            Hand::jokers(cards),
+            Hand::is_flush(cards),
+        )
+    }
+
+    pub(crate) fn from_ranks(entries: &[usize; 13], jokers: usize, is_flush: bool) -> Option<Self> {
+        let is_royal_straight = Hand::is_royal_straight(entries);
+        let is_straight = Hand::is_straight(entries);
+        let same_ranks = Hand::same_ranks(entries);
 
         if is_royal_straight && is_flush {
             Some(Hand::RoyalFlush)
@@ -32,7 +53,7 @@ impl Hand {
             Some(Hand::FiveOfAKind)
         } else if same_ranks + jokers == 4 {
             Some(Hand::FourOfAKind)
-        } else if Hand::is_full_house(cards) {
+        } else if Hand::is_full_house(entries, jokers) {
             Some(Hand::FullHouse)
         } else if is_flush {
             Some(Hand::Flush)
@@ -40,7 +61,7 @@ impl Hand {
             Some(Hand::Straight)
         } else if same_ranks + jokers == 3 {
             Some(Hand::ThreeOfAKind)
-        } else if Hand::pairs(&entries) == 2 {
+        } else if Hand::pairs(entries) == 2 {
             Some(Hand::TwoPair)
         } else if entries[0]
             .max(entries[10])
@@ -55,75 +76,52 @@ impl Hand {
         }
     }
 
-    fn is_royal_straight(cards: &[Card]) -> bool {
-        let mut ranks: Vec<u8> = cards
-            .iter()
-            .filter(|card| card.suit != Suit::Joker)
-            .map(|card| card.rank.value())
-            .collect();
-        ranks.sort();
-        ranks
+    fn is_royal_straight(entries: &[usize; 13]) -> bool {
+        entries
             .iter()
-            .all(|rank| matches!(rank, 1 | 10 | 11 | 12 | 13))
-            && ranks.windows(2).all(|s| s[0] != s[1])
+            .enumerate()
+            .all(|(i, count)| *count == 0 || (*count == 1 && matches!(i + 1, 1 | 10..=13)))
     }
 
-    fn is_straight(cards: &[Card]) -> bool {
-        let mut ranks: Vec<u8> = cards
-            .iter()
-            .filter(|card| card.suit != Suit::Joker)
-            .map(|card| card.rank.value())
-            .collect();
-        ranks.sort();
-
-        let range = ranks.last().unwrap() - ranks.first().unwrap();
-        let all_diff = ranks.windows(2).all(|x| x[0] != x[1]);
-        range < 5 && all_diff
+    fn is_straight(entries: &[usize; 13]) -> bool {
+        let first = entries.iter().position(|count| *count > 0);
+        let last = entries.iter().rposition(|count| *count > 0);
+        let range = match (first, last) {
+            (Some(first), Some(last)) => last - first,
+            _ => 0,
+        };
+        range < 5 && entries.iter().all(|count| *count <= 1)
     }
 
     fn is_flush(cards: &[Card]) -> bool {
-        cards
+        let mut suits = cards
             .iter()
             .filter(|card| card.suit != Suit::Joker)
-            .collect::<Vec<&Card>>()
-            .windows(2)
-            .all(|slice| slice[0].suit == slice[1].suit)
+            .map(|card| &card.suit);
+        match suits.next() {
+            Some(first) => suits.all(|suit| suit == first),
+            None => true,
+        }
     }
 
-    fn is_full_house(cards: &[Card]) -> bool {
-        cards
-            .iter()
-            .filter(|card| card.suit != Suit::Joker)
-            .fold(Vec::new(), |mut ranks, card| {
-                if !ranks.contains(&card.rank) {
-                    ranks.push(card.rank.clone());
-                }
-                ranks
-            })
-            .len()
-            == 2
+    fn is_full_house(entries: &[usize; 13], jokers: usize) -> bool {
+        let ranks = entries.iter().filter(|count| **count > 0).count();
+        entries.iter().sum::<usize>() + jokers == 5
+            && ranks == 2
+            && entries.iter().all(|count| *count <= 3)
     }
 
-    fn same_ranks(cards: &[Card]) -> u8 {
-        cards
-            .iter()
-            .filter(|card| card.suit != Suit::Joker)
-            .fold(vec![0; 13], |mut entries, card| {
-                entries[(card.rank.value() - 1) as usize] += 1;
-                entries
-            })
-            .into_iter()
-            .max()
-            .unwrap()
+    fn same_ranks(entries: &[usize; 13]) -> usize {
+        entries.iter().copied().max().unwrap()
     }
 
-    fn jokers(cards: &[Card]) -> u8 {
-        cards.iter().filter(|card| card.suit == Suit::Joker).count() as u8
+    fn jokers(cards: &[Card]) -> usize {
+        cards.iter().filter(|card| card.suit == Suit::Joker).count()
     }
 
-    fn count(cards: &[Card]) -> [u8; 13] {
+    fn count(cards: &[Card]) -> [usize; 13] {
         cards.iter().filter(|card| card.suit != Suit::Joker).fold(
-            [0_u8; 13],
+            [0_usize; 13],
             |mut entries, card| {
                 entries[(card.rank.value() - 1) as usize] += 1;
                 entries
@@ -131,7 +129,7 @@ impl Hand {
         )
     }
 
-    fn pairs(card_entries: &[u8; 13]) -> usize {
+    fn pairs(card_entries: &[usize; 13]) -> usize {
         card_entries.iter().filter(|count| count == &&2).count()
     }
 }
@@ -157,10 +155,43 @@ impl Display for Hand {
     }
 }
 
+impl FromStr for Hand {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Hand::ALL
+            .into_iter()
+            .find(|hand| hand.to_string() == s)
+            .ok_or("Invalid hand")
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod test {
     use super::*;
-    use crate::card::Rank;
+    use crate::card::{Rank, any_card};
+    #[cfg(feature = "std")]
+    use crate::{selftest::hand_counts, video_poker::Rule};
+    use alloc::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn from_cards_should_not_panic_on_any_size(
+            cards in proptest::collection::vec(any_card(), 0..300)
+        ) {
+            Hand::from_cards(&cards);
+        }
+
+        #[test]
+        fn from_cards_should_not_depend_on_the_order(
+            (cards, shuffled) in proptest::collection::vec(any_card(), 5)
+                .prop_flat_map(|cards| (Just(cards.clone()), Just(cards).prop_shuffle()))
+        ) {
+            prop_assert_eq!(Hand::from_cards(&cards), Hand::from_cards(&shuffled));
+        }
+    }
 
     #[test]
     fn royal_flush() {
@@ -901,4 +932,55 @@ mod test {
             Some(Hand::JacksOrBetter)
         );
     }
+
+    #[test]
+    fn full_house_should_need_a_three_and_a_pair() {
+        let cards = [
+            (Rank::Ace, Suit::Heart),
+            (Rank::Ace, Suit::Spade),
+            (Rank::King, Suit::Heart),
+            (Rank::King, Suit::Club),
+        ]
+        .map(|(rank, suit)| Card { rank, suit });
+
+        assert_eq!(Hand::from_cards(&cards[..3]), Some(Hand::JacksOrBetter));
+        assert_eq!(Hand::from_cards(&cards), Some(Hand::TwoPair));
+    }
+
+    #[test]
+    fn straight_should_not_panic_on_jokers_only() {
+        let jokers = [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five].map(|rank| Card {
+            rank,
+            suit: Suit::Joker,
+        });
+
+        assert_eq!(Hand::from_cards(&jokers), Some(Hand::RoyalFlush));
+    }
+
+    #[test]
+    fn hand_should_parse_what_it_displays() {
+        for hand in Hand::ALL {
+            assert_eq!(hand.to_string().parse(), Ok(hand));
+        }
+        assert!("Three of a kind".parse::<Hand>().is_err());
+    }
+
+    // Every 5-card deal, against the published frequencies for one deck and,
+    // with two jokers, the best hand over every substitution of the jokers.
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_cards_should_match_the_combinatorial_frequencies() {
+        assert_eq!(
+            hand_counts(Rule::Default52Cards),
+            vec![
+                4, 36, 0, 624, 3744, 5108, 10200, 54912, 123552, 337920, 2062860
+            ]
+        );
+        assert_eq!(
+            hand_counts(Rule::Jokers54Cards),
+            vec![
+                84, 540, 78, 9360, 9360, 11388, 34704, 232968, 123552, 624696, 2115780
+            ]
+        );
+    }
 }
@@ -1,7 +1,9 @@
+mod feature;
 mod game;
 mod hand;
 mod player;
 
-pub use game::{Rule, VideoPoker};
+pub use feature::{Feature, MultiStrike, SuperTimesPay};
+pub use game::{Round, Rule, Seed, VideoPoker};
 pub use hand::Hand;
-pub use player::Player;
+pub use player::{AsyncPlayer, Player};
@@ -1,7 +1,70 @@
 use crate::card::Card;
+use alloc::vec::Vec;
+use core::future::ready;
 
 pub trait Player {
     fn show_cards(&self, cards: &[Card]);
 
     fn exchange(&self, cards: &[Card]) -> Vec<usize>;
+
+    fn show_hands(&self, hands: &[Vec<Card>]) {
+        hands.iter().for_each(|cards| self.show_cards(cards));
+    }
+}
+
+pub trait AsyncPlayer: Sync {
+    fn show_cards(&self, cards: &[Card]) -> impl Future<Output = ()> + Send;
+
+    fn exchange(&self, cards: &[Card]) -> impl Future<Output = Vec<usize>> + Send;
+
+    fn show_hands(&self, hands: &[Vec<Card>]) -> impl Future<Output = ()> + Send {
+        async move {
+            for cards in hands {
+                self.show_cards(cards).await;
+            }
+        }
+    }
+}
+
+// The turns of a round, so one game loop serves blocking and async players.
+pub(crate) trait Turns {
+    fn show_cards(&self, cards: &[Card]) -> impl Future<Output = ()>;
+
+    fn exchange(&self, cards: &[Card]) -> impl Future<Output = Vec<usize>>;
+
+    fn show_hands(&self, hands: &[Vec<Card>]) -> impl Future<Output = ()>;
+}
+
+pub(crate) struct Blocking<'a, P: ?Sized>(pub(crate) &'a P);
+
+pub(crate) struct Awaiting<'a, P: ?Sized>(pub(crate) &'a P);
+
+impl<P: Player + ?Sized> Turns for Blocking<'_, P> {
+    fn show_cards(&self, cards: &[Card]) -> impl Future<Output = ()> {
+        self.0.show_cards(cards);
+        ready(())
+    }
+
+    fn exchange(&self, cards: &[Card]) -> impl Future<Output = Vec<usize>> {
+        ready(self.0.exchange(cards))
+    }
+
+    fn show_hands(&self, hands: &[Vec<Card>]) -> impl Future<Output = ()> {
+        self.0.show_hands(hands);
+        ready(())
+    }
+}
+
+impl<P: AsyncPlayer + ?Sized> Turns for Awaiting<'_, P> {
+    fn show_cards(&self, cards: &[Card]) -> impl Future<Output = ()> {
+        self.0.show_cards(cards)
+    }
+
+    fn exchange(&self, cards: &[Card]) -> impl Future<Output = Vec<usize>> {
+        self.0.exchange(cards)
+    }
+
+    fn show_hands(&self, hands: &[Vec<Card>]) -> impl Future<Output = ()> {
+        self.0.show_hands(hands)
+    }
 }
@@ -0,0 +1,23 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// The header is generated into OUT_DIR. Set VIDEO_POKER_UPDATE_HEADER to copy
+// it over the tracked include/video_poker.h.
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let header = PathBuf::from(env::var("OUT_DIR").unwrap()).join("video_poker.h");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=VIDEO_POKER_UPDATE_HEADER");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(cbindgen::Config::from_root_or_default(&crate_dir))
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file(&header);
+    if env::var_os("VIDEO_POKER_UPDATE_HEADER").is_some() {
+        fs::copy(&header, format!("{}/include/video_poker.h", crate_dir))
+            .expect("Unable to copy the C header");
+    }
+}
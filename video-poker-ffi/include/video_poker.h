@@ -0,0 +1,173 @@
+#ifndef VIDEO_POKER_H
+#define VIDEO_POKER_H
+
+/* Generated by cbindgen from video-poker-ffi, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define VP_SUIT_HEART 0
+
+#define VP_SUIT_SPADE 1
+
+#define VP_SUIT_DIAMOND 2
+
+#define VP_SUIT_CLUB 3
+
+#define VP_SUIT_JOKER 4
+
+enum VpStatus
+#if __STDC_VERSION__ >= 202311L
+  : int32_t
+#endif // __STDC_VERSION__ >= 202311L
+ {
+  VP_STATUS_OK = 0,
+  VP_STATUS_NULL_POINTER = 1,
+  VP_STATUS_INVALID_ARGUMENT = 2,
+  VP_STATUS_NOT_DEALT = 3,
+  VP_STATUS_ALREADY_DEALT = 4,
+  VP_STATUS_NO_ENOUGH_CREDITS = 5,
+  VP_STATUS_TOO_MANY_CREDITS = 6,
+  VP_STATUS_PANIC = 7,
+};
+#if __STDC_VERSION__ >= 202311L
+typedef enum VpStatus VpStatus;
+#else
+typedef int32_t VpStatus;
+#endif // __STDC_VERSION__ >= 202311L
+
+enum VpHand
+#if __STDC_VERSION__ >= 202311L
+  : int32_t
+#endif // __STDC_VERSION__ >= 202311L
+ {
+  VP_HAND_NONE = -1,
+  VP_HAND_ROYAL_FLUSH = 0,
+  VP_HAND_STRAIGHT_FLUSH = 1,
+  VP_HAND_FIVE_OF_A_KIND = 2,
+  VP_HAND_FOUR_OF_A_KIND = 3,
+  VP_HAND_FULL_HOUSE = 4,
+  VP_HAND_FLUSH = 5,
+  VP_HAND_STRAIGHT = 6,
+  VP_HAND_THREE_OF_A_KIND = 7,
+  VP_HAND_TWO_PAIR = 8,
+  VP_HAND_JACKS_OR_BETTER = 9,
+};
+#if __STDC_VERSION__ >= 202311L
+typedef enum VpHand VpHand;
+#else
+typedef int32_t VpHand;
+#endif // __STDC_VERSION__ >= 202311L
+
+typedef struct VpGame VpGame;
+
+/**
+ * Suit is one of the `VP_SUIT_*` constants, rank runs from 1 (ace) to 13 (king).
+ */
+typedef struct VpCard {
+  uint8_t suit;
+  uint8_t rank;
+} VpCard;
+
+/**
+ * `win_cap` is 0 when there is no cap, `bet` is 0 when no round is in progress.
+ */
+typedef struct VpScoreBoard {
+  uint64_t credits;
+  uint64_t win_cap;
+  uint64_t bet;
+} VpScoreBoard;
+
+/**
+ * Creates a game with the given starting credits, or returns NULL on failure.
+ * Release it with `vp_game_free`.
+ */
+struct VpGame *vp_game_new(bool jokers, uint64_t credits);
+
+/**
+ * # Safety
+ *
+ * `game` must be NULL or a pointer returned by `vp_game_new` that was not freed yet.
+ */
+void vp_game_free(struct VpGame *game);
+
+/**
+ * Charges `bet` credits and deals five cards into `cards`.
+ *
+ * # Safety
+ *
+ * `game` must come from `vp_game_new` and `cards` must point to 5 writable cards.
+ */
+VpStatus vp_game_deal(struct VpGame *game, uint64_t bet, struct VpCard *cards);
+
+/**
+ * Same as `vp_game_deal` with a fixed seed, so a round can be replayed.
+ *
+ * # Safety
+ *
+ * `game` must come from `vp_game_new` and `cards` must point to 5 writable cards.
+ */
+VpStatus vp_game_deal_seeded(struct VpGame *game,
+                             uint64_t bet,
+                             uint64_t seed,
+                             struct VpCard *cards);
+
+/**
+ * Holds or releases the card at `index` (0 to 4) before the draw.
+ *
+ * # Safety
+ *
+ * `game` must come from `vp_game_new`.
+ */
+VpStatus vp_game_hold(struct VpGame *game, size_t index, bool held);
+
+/**
+ * Replaces the cards that are not held, pays the hand and writes the final cards, the hand
+ * and the credits won.
+ *
+ * # Safety
+ *
+ * `game` must come from `vp_game_new`, `cards` must point to 5 writable cards, `hand` and
+ * `prize` must be writable.
+ */
+VpStatus vp_game_draw(struct VpGame *game, struct VpCard *cards, VpHand *hand, uint64_t *prize);
+
+/**
+ * Evaluates a hand of `len` cards without touching any game. `len` must be 5.
+ *
+ * # Safety
+ *
+ * `cards` must point to `len` readable cards and `hand` must be writable.
+ */
+VpStatus vp_evaluate(const struct VpCard *cards, size_t len, VpHand *hand);
+
+/**
+ * # Safety
+ *
+ * `game` must come from `vp_game_new` and `score_board` must be writable.
+ */
+VpStatus vp_game_score_board(const struct VpGame *game, struct VpScoreBoard *score_board);
+
+/**
+ * Writes how many credits a hand pays per credit bet.
+ *
+ * # Safety
+ *
+ * `game` must come from `vp_game_new` and `rate` must be writable.
+ */
+VpStatus vp_game_pay_rate(const struct VpGame *game, int32_t hand, uint64_t *rate);
+
+/**
+ * Returns the display name of a `VP_HAND_*` value, or NULL for anything else.
+ */
+const char *vp_hand_name(int32_t hand);
+
+/**
+ * Returns a description of a `VP_STATUS_*` value.
+ */
+const char *vp_status_message(int32_t status);
+
+#endif  /* VIDEO_POKER_H */
@@ -0,0 +1,397 @@
+use std::ffi::{CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::OnceLock;
+use video_poker_core::score_board::{Rate, ScoreBoard};
+use video_poker_core::video_poker::{Hand, Rule, VideoPoker};
+use video_poker_core::{Card, Money, Rank, Suit};
+
+pub const VP_SUIT_HEART: u8 = 0;
+pub const VP_SUIT_SPADE: u8 = 1;
+pub const VP_SUIT_DIAMOND: u8 = 2;
+pub const VP_SUIT_CLUB: u8 = 3;
+pub const VP_SUIT_JOKER: u8 = 4;
+
+const SUITS: [Suit; 5] = [
+    Suit::Heart,
+    Suit::Spade,
+    Suit::Diamond,
+    Suit::Club,
+    Suit::Joker,
+];
+const RANKS: [Rank; 13] = [
+    Rank::Ace,
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+];
+const HAND_SIZE: usize = 5;
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VpStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidArgument = 2,
+    NotDealt = 3,
+    AlreadyDealt = 4,
+    NoEnoughCredits = 5,
+    TooManyCredits = 6,
+    Panic = 7,
+}
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VpHand {
+    None = -1,
+    RoyalFlush = 0,
+    StraightFlush = 1,
+    FiveOfAKind = 2,
+    FourOfAKind = 3,
+    FullHouse = 4,
+    Flush = 5,
+    Straight = 6,
+    ThreeOfAKind = 7,
+    TwoPair = 8,
+    JacksOrBetter = 9,
+}
+
+/// Suit is one of the `VP_SUIT_*` constants, rank runs from 1 (ace) to 13 (king).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VpCard {
+    pub suit: u8,
+    pub rank: u8,
+}
+
+/// `win_cap` is 0 when there is no cap, `bet` is 0 when no round is in progress.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VpScoreBoard {
+    pub credits: u64,
+    pub win_cap: u64,
+    pub bet: u64,
+}
+
+pub struct VpGame {
+    game: VideoPoker,
+    score_board: ScoreBoard,
+    held: [bool; HAND_SIZE],
+    bet: Money,
+}
+
+impl VpCard {
+    fn new(card: &Card) -> Self {
+        VpCard {
+            suit: SUITS.iter().position(|suit| *suit == card.suit).unwrap() as u8,
+            rank: card.rank.value(),
+        }
+    }
+
+    fn card(&self) -> Result<Card, VpStatus> {
+        let suit = SUITS.get(self.suit as usize);
+        let rank = (self.rank as usize)
+            .checked_sub(1)
+            .and_then(|i| RANKS.get(i));
+        match (suit, rank) {
+            (Some(suit), Some(rank)) => Ok(Card {
+                suit: suit.clone(),
+                rank: rank.clone(),
+            }),
+            _ => Err(VpStatus::InvalidArgument),
+        }
+    }
+}
+
+impl VpHand {
+    fn new(hand: Option<Hand>) -> Self {
+        match hand {
+            Some(Hand::RoyalFlush) => VpHand::RoyalFlush,
+            Some(Hand::StraightFlush) => VpHand::StraightFlush,
+            Some(Hand::FiveOfAKind) => VpHand::FiveOfAKind,
+            Some(Hand::FourOfAKind) => VpHand::FourOfAKind,
+            Some(Hand::FullHouse) => VpHand::FullHouse,
+            Some(Hand::Flush) => VpHand::Flush,
+            Some(Hand::Straight) => VpHand::Straight,
+            Some(Hand::ThreeOfAKind) => VpHand::ThreeOfAKind,
+            Some(Hand::TwoPair) => VpHand::TwoPair,
+            Some(Hand::JacksOrBetter) => VpHand::JacksOrBetter,
+            None => VpHand::None,
+        }
+    }
+}
+
+/// Creates a game with the given starting credits, or returns NULL on failure.
+/// Release it with `vp_game_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn vp_game_new(jokers: bool, credits: u64) -> *mut VpGame {
+    let game = panic::catch_unwind(|| {
+        Some(VpGame {
+            game: VideoPoker::new(if jokers {
+                Rule::Jokers54Cards
+            } else {
+                Rule::Default52Cards
+            }),
+            score_board: ScoreBoard::new(
+                Money::new(usize::try_from(credits).ok()?),
+                Rate::JOKER_POKER,
+            ),
+            held: [false; HAND_SIZE],
+            bet: Money::ZERO,
+        })
+    });
+    match game {
+        Ok(Some(game)) => Box::into_raw(Box::new(game)),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// # Safety
+///
+/// `game` must be NULL or a pointer returned by `vp_game_new` that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_game_free(game: *mut VpGame) {
+    if !game.is_null() {
+        drop(unsafe { Box::from_raw(game) });
+    }
+}
+
+/// Charges `bet` credits and deals five cards into `cards`.
+///
+/// # Safety
+///
+/// `game` must come from `vp_game_new` and `cards` must point to 5 writable cards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_game_deal(game: *mut VpGame, bet: u64, cards: *mut VpCard) -> VpStatus {
+    unsafe { vp_game_deal_seeded(game, bet, rand::random(), cards) }
+}
+
+/// Same as `vp_game_deal` with a fixed seed, so a round can be replayed.
+///
+/// # Safety
+///
+/// `game` must come from `vp_game_new` and `cards` must point to 5 writable cards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_game_deal_seeded(
+    game: *mut VpGame,
+    bet: u64,
+    seed: u64,
+    cards: *mut VpCard,
+) -> VpStatus {
+    guard(|| {
+        let game = unsafe { game.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        let cards = unsafe { output(cards, HAND_SIZE) }?;
+        let bet = Money::new(usize::try_from(bet).map_err(|_| VpStatus::InvalidArgument)?);
+        if bet == Money::ZERO {
+            return Err(VpStatus::InvalidArgument);
+        }
+        if game.game.is_dealt() {
+            return Err(VpStatus::AlreadyDealt);
+        }
+        game.score_board
+            .cost(bet)
+            .map_err(|_| VpStatus::NoEnoughCredits)?;
+        game.bet = bet;
+        game.held = [false; HAND_SIZE];
+        write_cards(cards, game.game.deal(seed));
+        Ok(())
+    })
+}
+
+/// Holds or releases the card at `index` (0 to 4) before the draw.
+///
+/// # Safety
+///
+/// `game` must come from `vp_game_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_game_hold(game: *mut VpGame, index: usize, held: bool) -> VpStatus {
+    guard(|| {
+        let game = unsafe { game.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        if !game.game.is_dealt() {
+            return Err(VpStatus::NotDealt);
+        }
+        *game.held.get_mut(index).ok_or(VpStatus::InvalidArgument)? = held;
+        Ok(())
+    })
+}
+
+/// Replaces the cards that are not held, pays the hand and writes the final cards, the hand
+/// and the credits won.
+///
+/// # Safety
+///
+/// `game` must come from `vp_game_new`, `cards` must point to 5 writable cards, `hand` and
+/// `prize` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_game_draw(
+    game: *mut VpGame,
+    cards: *mut VpCard,
+    hand: *mut VpHand,
+    prize: *mut u64,
+) -> VpStatus {
+    guard(|| {
+        let game = unsafe { game.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        let cards = unsafe { output(cards, HAND_SIZE) }?;
+        let hand = unsafe { hand.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        let prize = unsafe { prize.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        let exchange = (0..HAND_SIZE).filter(|i| !game.held[*i]).collect();
+        let result = game
+            .game
+            .draw(1, exchange)
+            .map_err(|_| VpStatus::NotDealt)?
+            .pop()
+            .flatten();
+        let bet = std::mem::take(&mut game.bet);
+        write_cards(cards, game.game.cards());
+        *hand = VpHand::new(result);
+        *prize = 0;
+        if let Some(result) = result {
+            let win = game
+                .score_board
+                .prize_hands(&mut game.game, bet, &[Some(result)]);
+            *prize = game
+                .score_board
+                .apply_win(win)
+                .map_err(|_| VpStatus::TooManyCredits)?
+                .amount() as u64;
+        }
+        Ok(())
+    })
+}
+
+/// Evaluates a hand of `len` cards without touching any game. `len` must be 5.
+///
+/// # Safety
+///
+/// `cards` must point to `len` readable cards and `hand` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_evaluate(
+    cards: *const VpCard,
+    len: usize,
+    hand: *mut VpHand,
+) -> VpStatus {
+    guard(|| {
+        if cards.is_null() {
+            return Err(VpStatus::NullPointer);
+        }
+        let hand = unsafe { hand.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        if len != HAND_SIZE {
+            return Err(VpStatus::InvalidArgument);
+        }
+        let cards = unsafe { std::slice::from_raw_parts(cards, len) }
+            .iter()
+            .map(VpCard::card)
+            .collect::<Result<Vec<_>, _>>()?;
+        *hand = VpHand::new(Hand::from_cards(&cards));
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// `game` must come from `vp_game_new` and `score_board` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_game_score_board(
+    game: *const VpGame,
+    score_board: *mut VpScoreBoard,
+) -> VpStatus {
+    guard(|| {
+        let game = unsafe { game.as_ref() }.ok_or(VpStatus::NullPointer)?;
+        let score_board = unsafe { score_board.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        *score_board = VpScoreBoard {
+            credits: game.score_board.token().amount() as u64,
+            win_cap: game
+                .score_board
+                .win_cap()
+                .map_or(0, |cap| cap.amount() as u64),
+            bet: game.bet.amount() as u64,
+        };
+        Ok(())
+    })
+}
+
+/// Writes how many credits a hand pays per credit bet.
+///
+/// # Safety
+///
+/// `game` must come from `vp_game_new` and `rate` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vp_game_pay_rate(
+    game: *const VpGame,
+    hand: i32,
+    rate: *mut u64,
+) -> VpStatus {
+    guard(|| {
+        let game = unsafe { game.as_ref() }.ok_or(VpStatus::NullPointer)?;
+        let rate = unsafe { rate.as_mut() }.ok_or(VpStatus::NullPointer)?;
+        let hand = usize::try_from(hand)
+            .ok()
+            .and_then(|i| Hand::ALL.get(i))
+            .ok_or(VpStatus::InvalidArgument)?;
+        *rate = game.score_board.rate().by_hand(hand) as u64;
+        Ok(())
+    })
+}
+
+/// Returns the display name of a `VP_HAND_*` value, or NULL for anything else.
+#[unsafe(no_mangle)]
+pub extern "C" fn vp_hand_name(hand: i32) -> *const c_char {
+    static NAMES: OnceLock<Vec<CString>> = OnceLock::new();
+    let names = NAMES.get_or_init(|| {
+        Hand::ALL
+            .iter()
+            .map(|hand| CString::new(hand.to_string()).unwrap())
+            .collect()
+    });
+    usize::try_from(hand)
+        .ok()
+        .and_then(|i| names.get(i))
+        .map_or(std::ptr::null(), |name| name.as_ptr())
+}
+
+/// Returns a description of a `VP_STATUS_*` value.
+#[unsafe(no_mangle)]
+pub extern "C" fn vp_status_message(status: i32) -> *const c_char {
+    match status {
+        0 => c"Ok",
+        1 => c"Null pointer",
+        2 => c"Invalid argument",
+        3 => c"No cards dealt",
+        4 => c"Cards already dealt",
+        5 => c"No enough credits",
+        6 => c"Too many credits",
+        7 => c"Internal error",
+        _ => c"Unknown status",
+    }
+    .as_ptr()
+}
+
+fn guard(f: impl FnOnce() -> Result<(), VpStatus>) -> VpStatus {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => VpStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => VpStatus::Panic,
+    }
+}
+
+unsafe fn output<'a>(cards: *mut VpCard, len: usize) -> Result<&'a mut [VpCard], VpStatus> {
+    if cards.is_null() {
+        return Err(VpStatus::NullPointer);
+    }
+    Ok(unsafe { std::slice::from_raw_parts_mut(cards, len) })
+}
+
+fn write_cards(output: &mut [VpCard], cards: &[Card]) {
+    output
+        .iter_mut()
+        .zip(cards)
+        .for_each(|(output, card)| *output = VpCard::new(card));
+}
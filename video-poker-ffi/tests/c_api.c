@@ -0,0 +1,105 @@
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "video_poker.h"
+
+static void evaluate_should_rank_hands(void) {
+  VpCard royal[5] = {{VP_SUIT_HEART, 1},
+                     {VP_SUIT_HEART, 13},
+                     {VP_SUIT_HEART, 12},
+                     {VP_SUIT_HEART, 11},
+                     {VP_SUIT_HEART, 10}};
+  VpCard nothing[5] = {{VP_SUIT_HEART, 2},
+                       {VP_SUIT_SPADE, 5},
+                       {VP_SUIT_CLUB, 9},
+                       {VP_SUIT_DIAMOND, 11},
+                       {VP_SUIT_HEART, 13}};
+  VpCard invalid[5] = {{VP_SUIT_HEART, 14},
+                       {VP_SUIT_SPADE, 5},
+                       {VP_SUIT_CLUB, 9},
+                       {VP_SUIT_DIAMOND, 11},
+                       {VP_SUIT_HEART, 13}};
+  VpHand hand;
+
+  assert(vp_evaluate(royal, 5, &hand) == VP_STATUS_OK);
+  assert(hand == VP_HAND_ROYAL_FLUSH);
+  assert(strcmp(vp_hand_name(hand), "Royal Flush") == 0);
+  assert(vp_evaluate(nothing, 5, &hand) == VP_STATUS_OK);
+  assert(hand == VP_HAND_NONE);
+  assert(vp_hand_name(hand) == NULL);
+  assert(vp_evaluate(invalid, 5, &hand) == VP_STATUS_INVALID_ARGUMENT);
+  assert(vp_evaluate(royal, 0, &hand) == VP_STATUS_INVALID_ARGUMENT);
+  assert(vp_evaluate(royal, 2, &hand) == VP_STATUS_INVALID_ARGUMENT);
+  assert(vp_evaluate(NULL, 5, &hand) == VP_STATUS_NULL_POINTER);
+}
+
+static void game_should_report_errors(void) {
+  VpGame *game = vp_game_new(false, 100);
+  VpCard cards[5];
+  VpHand hand;
+  uint64_t prize;
+
+  assert(game != NULL);
+  assert(vp_game_draw(game, cards, &hand, &prize) == VP_STATUS_NOT_DEALT);
+  assert(vp_game_hold(game, 0, true) == VP_STATUS_NOT_DEALT);
+  assert(vp_game_deal(game, 0, cards) == VP_STATUS_INVALID_ARGUMENT);
+  assert(vp_game_deal(game, 101, cards) == VP_STATUS_NO_ENOUGH_CREDITS);
+  assert(vp_game_deal(NULL, 10, cards) == VP_STATUS_NULL_POINTER);
+  assert(vp_game_deal(game, 10, NULL) == VP_STATUS_NULL_POINTER);
+  assert(vp_game_deal(game, 10, cards) == VP_STATUS_OK);
+  assert(vp_game_deal(game, 10, cards) == VP_STATUS_ALREADY_DEALT);
+  assert(vp_game_hold(game, 5, true) == VP_STATUS_INVALID_ARGUMENT);
+  assert(strcmp(vp_status_message(VP_STATUS_NOT_DEALT), "No cards dealt") == 0);
+  vp_game_free(game);
+  vp_game_free(NULL);
+}
+
+static void game_should_play_a_round(void) {
+  VpGame *game = vp_game_new(true, 100);
+  VpGame *replay = vp_game_new(true, 100);
+  VpCard dealt[5], replayed[5], drawn[5];
+  VpScoreBoard score_board;
+  VpHand hand, evaluated;
+  uint64_t prize, rate;
+
+  assert(vp_game_deal_seeded(game, 10, 42, dealt) == VP_STATUS_OK);
+  assert(vp_game_deal_seeded(replay, 10, 42, replayed) == VP_STATUS_OK);
+  assert(memcmp(dealt, replayed, sizeof(dealt)) == 0);
+  assert(vp_game_score_board(game, &score_board) == VP_STATUS_OK);
+  assert(score_board.credits == 90);
+  assert(score_board.bet == 10);
+  assert(score_board.win_cap == 0);
+
+  for (size_t i = 0; i < 5; i++) {
+    assert(vp_game_hold(game, i, true) == VP_STATUS_OK);
+  }
+  assert(vp_game_draw(game, drawn, &hand, &prize) == VP_STATUS_OK);
+  assert(memcmp(dealt, drawn, sizeof(dealt)) == 0);
+  assert(vp_evaluate(drawn, 5, &evaluated) == VP_STATUS_OK);
+  assert(hand == evaluated);
+  if (hand == VP_HAND_NONE) {
+    assert(prize == 0);
+  } else {
+    assert(vp_game_pay_rate(game, hand, &rate) == VP_STATUS_OK);
+    assert(prize == rate * 10);
+  }
+  assert(vp_game_score_board(game, &score_board) == VP_STATUS_OK);
+  assert(score_board.credits == 90 + prize);
+  assert(score_board.bet == 0);
+  assert(vp_game_draw(game, drawn, &hand, &prize) == VP_STATUS_NOT_DEALT);
+
+  assert(vp_game_pay_rate(game, VP_HAND_ROYAL_FLUSH, &rate) == VP_STATUS_OK);
+  assert(rate == 500);
+  assert(vp_game_pay_rate(game, VP_HAND_NONE, &rate) == VP_STATUS_INVALID_ARGUMENT);
+  vp_game_free(game);
+  vp_game_free(replay);
+}
+
+int main(void) {
+  evaluate_should_rank_hands();
+  game_should_report_errors();
+  game_should_play_a_round();
+  puts("ok");
+  return 0;
+}
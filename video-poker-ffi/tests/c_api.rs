@@ -0,0 +1,54 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// System libraries the Rust standard library pulls into the static library.
+// Other hosts link them by default.
+#[cfg(target_os = "linux")]
+const NATIVE_LIBS: &[&str] = &["-lpthread", "-ldl", "-lm"];
+#[cfg(not(target_os = "linux"))]
+const NATIVE_LIBS: &[&str] = &[];
+
+#[test]
+fn c_program_should_use_the_header_and_library() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Cargo builds the static library for this test next to the test binary in target/*/deps.
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let binary = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("c_api");
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(env!("OUT_DIR"))
+        .arg(crate_dir.join("tests/c_api.c"))
+        .arg(deps.join("libvideo_poker_ffi.a"))
+        .args(NATIVE_LIBS)
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .expect("Unable to run the C compiler");
+    assert!(status.success());
+
+    let output = Command::new(&binary).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
+
+#[test]
+fn tracked_header_should_match_the_generated_one() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let generated = fs::read_to_string(PathBuf::from(env!("OUT_DIR")).join("video_poker.h"));
+    let tracked = fs::read_to_string(crate_dir.join("include/video_poker.h"));
+
+    assert!(
+        generated.unwrap() == tracked.unwrap(),
+        "include/video_poker.h is stale, rebuild with VIDEO_POKER_UPDATE_HEADER=1"
+    );
+}
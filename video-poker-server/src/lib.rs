@@ -0,0 +1,4 @@
+mod server;
+mod session;
+
+pub use server::Server;
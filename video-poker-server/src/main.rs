@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+use video_poker_server::Server;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let address = args.get(1).map(String::as_str).unwrap_or("127.0.0.1:8080");
+    let server = match args.get(2) {
+        Some(data) => Server::new().with_data(PathBuf::from(data)),
+        None => Ok(Server::new()),
+    };
+    match server {
+        Ok(server) => {
+            println!("Listening on http://{}", address);
+            if let Err(e) = server.run(address) {
+                println!("{}", e);
+            }
+        }
+        Err(e) => println!("Failed to load the sessions: {}", e),
+    }
+}
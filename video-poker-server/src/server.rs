@@ -0,0 +1,399 @@
+use crate::session::{Saved, Session};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tiny_http::{Header, Method, Response};
+use video_poker_core::Money;
+use video_poker_core::bonus_game::{Choice, Guess};
+
+pub struct Server {
+    sessions: HashMap<String, Session>,
+    data: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct BetRequest {
+    amount: usize,
+}
+
+#[derive(Deserialize)]
+struct HoldRequest {
+    held: Vec<usize>,
+}
+
+#[derive(Deserialize)]
+struct DoubleRequest {
+    choice: Choice,
+    gamble: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GuessRequest {
+    guess: Guess,
+}
+
+type Reply = (u16, serde_json::Value);
+
+impl Server {
+    const CREDITS: Money = Money::new(200);
+
+    pub fn new() -> Self {
+        Server {
+            sessions: HashMap::new(),
+            data: None,
+        }
+    }
+
+    pub fn with_data(mut self, data: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&data)?;
+        for entry in fs::read_dir(&data)? {
+            let path = entry?.path();
+            let (Some(id), Some("json")) = (
+                path.file_stem().and_then(|stem| stem.to_str()),
+                path.extension().and_then(|extension| extension.to_str()),
+            ) else {
+                continue;
+            };
+            let saved: Saved = serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            self.sessions
+                .insert(id.to_string(), Session::restore(saved));
+        }
+        self.data = Some(data);
+        Ok(self)
+    }
+
+    pub fn run(mut self, address: &str) -> io::Result<()> {
+        let server = tiny_http::Server::http(address).map_err(io::Error::other)?;
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let (status, reply) = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) if request.method() == &Method::Options => (204, String::new()),
+                Ok(_) => {
+                    let (status, reply) = self.handle(request.method(), request.url(), &body);
+                    (status, reply.to_string())
+                }
+                Err(e) => (400, json!({ "error": e.to_string() }).to_string()),
+            };
+            let response = Response::from_string(reply)
+                .with_status_code(status)
+                .with_header(Server::header("Content-Type", "application/json"))
+                .with_header(Server::header("Access-Control-Allow-Origin", "*"))
+                .with_header(Server::header(
+                    "Access-Control-Allow-Methods",
+                    "GET, POST, OPTIONS",
+                ))
+                .with_header(Server::header(
+                    "Access-Control-Allow-Headers",
+                    "Content-Type",
+                ));
+            if let Err(e) = request.respond(response) {
+                eprintln!("Failed to respond: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn handle(&mut self, method: &Method, url: &str, body: &str) -> Reply {
+        let path: Vec<&str> = url
+            .split('?')
+            .next()
+            .unwrap_or("")
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .collect();
+        match (method, &path[..]) {
+            (Method::Post, ["sessions"]) => {
+                let id = format!("{:016x}", rand::random::<u64>());
+                self.sessions
+                    .insert(id.clone(), Session::new(Server::CREDITS));
+                self.persist(&id);
+                let session = &self.sessions[&id];
+                (201, json!({ "id": id, "session": session.view() }))
+            }
+            (_, ["sessions", id, rest @ ..]) => {
+                let id = id.to_string();
+                let Some(session) = self.sessions.get_mut(&id) else {
+                    return Server::error(404, "Unknown session");
+                };
+                let result = match (method, rest) {
+                    (Method::Get, []) => Ok(()),
+                    (Method::Get, ["history"]) => {
+                        return (200, json!({ "history": session.history() }));
+                    }
+                    (Method::Post, ["bet"]) => Server::parse(body)
+                        .and_then(|request: BetRequest| session.bet(request.amount)),
+                    (Method::Post, ["deal"]) => session.deal(),
+                    (Method::Post, ["hold"]) => Server::parse(body)
+                        .and_then(|request: HoldRequest| session.hold(&request.held)),
+                    (Method::Post, ["double"]) => {
+                        Server::parse(body).and_then(|request: DoubleRequest| {
+                            session.double(request.choice, request.gamble.as_deref())
+                        })
+                    }
+                    (Method::Post, ["guess"]) => Server::parse(body)
+                        .and_then(|request: GuessRequest| session.guess(request.guess)),
+                    (Method::Post, ["cashout"]) => return self.cash_out(&id),
+                    _ => return Server::error(404, "Not found"),
+                };
+                self.persist(&id);
+                let session = &self.sessions[&id];
+                match result {
+                    Ok(()) => (200, json!({ "session": session.view() })),
+                    Err(e) => (400, json!({ "error": e, "session": session.view() })),
+                }
+            }
+            _ => Server::error(404, "Not found"),
+        }
+    }
+
+    fn cash_out(&mut self, id: &str) -> Reply {
+        if self.sessions[id].in_round() {
+            return Server::error(400, "Finish the round before cashing out");
+        }
+        let session = self.sessions.remove(id).unwrap();
+        if let Some(data) = self.data.as_ref()
+            && let Err(e) = fs::remove_file(data.join(format!("{}.json", id)))
+            && e.kind() != io::ErrorKind::NotFound
+        {
+            eprintln!("Failed to remove session {}: {}", id, e);
+        }
+        (200, json!({ "credits": session.credits() }))
+    }
+
+    fn persist(&self, id: &str) {
+        let Some(data) = self.data.as_ref() else {
+            return;
+        };
+        let saved = serde_json::to_string(&self.sessions[id].save()).unwrap();
+        if let Err(e) = fs::write(data.join(format!("{}.json", id)), saved) {
+            eprintln!("Failed to save session {}: {}", id, e);
+        }
+    }
+
+    fn parse<T: DeserializeOwned>(body: &str) -> Result<T, &'static str> {
+        serde_json::from_str(body).map_err(|_| "Invalid request body")
+    }
+
+    fn error(status: u16, reason: &str) -> Reply {
+        (status, json!({ "error": reason }))
+    }
+
+    fn header(name: &str, value: &str) -> Header {
+        Header::from_bytes(name, value).unwrap()
+    }
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::Value;
+
+    fn post(server: &mut Server, url: &str, body: &str) -> Reply {
+        server.handle(&Method::Post, url, body)
+    }
+
+    fn open(server: &mut Server, credits: usize) -> String {
+        let (status, reply) = post(server, "/sessions", "");
+        assert_eq!(status, 201);
+        let id = reply["id"].as_str().unwrap().to_string();
+        server
+            .sessions
+            .insert(id.clone(), Session::new(Money::new(credits)));
+        id
+    }
+
+    fn session(reply: &Reply) -> &Value {
+        &reply.1["session"]
+    }
+
+    // Deals and holds every card until a hand wins.
+    fn win(server: &mut Server, id: &str) -> Reply {
+        loop {
+            assert_eq!(post(server, &format!("/sessions/{}/deal", id), "").0, 200);
+            let reply = post(
+                server,
+                &format!("/sessions/{}/hold", id),
+                r#"{"held":[0,1,2,3,4]}"#,
+            );
+            assert_eq!(reply.0, 200);
+            if session(&reply)["phase"] == "bonus" {
+                return reply;
+            }
+        }
+    }
+
+    #[test]
+    fn bet_should_only_accept_the_listed_amounts() {
+        let mut server = Server::new();
+        let id = open(&mut server, 200);
+        let url = format!("/sessions/{}/bet", id);
+
+        let reply = post(&mut server, &url, r#"{"amount":100}"#);
+        assert_eq!(reply.0, 200);
+        assert_eq!(session(&reply)["bet"], 100);
+        let reply = post(&mut server, &url, r#"{"amount":7}"#);
+        assert_eq!((reply.0, &reply.1["error"]), (400, &json!("Invalid bet")));
+        let reply = post(&mut server, &url, "amount");
+        assert_eq!(reply.1["error"], "Invalid request body");
+    }
+
+    #[test]
+    fn deal_should_charge_the_bet_and_wait_for_holds() {
+        let mut server = Server::new();
+        let id = open(&mut server, 200);
+
+        let reply = post(&mut server, &format!("/sessions/{}/deal", id), "");
+        assert_eq!(reply.0, 200);
+        assert_eq!(session(&reply)["credits"], 150);
+        assert_eq!(session(&reply)["phase"], "dealt");
+        assert_eq!(session(&reply)["cards"].as_array().unwrap().len(), 5);
+        let reply = post(&mut server, &format!("/sessions/{}/deal", id), "");
+        assert_eq!(
+            (reply.0, &reply.1["error"]),
+            (400, &json!("Waiting for holds"))
+        );
+    }
+
+    #[test]
+    fn steps_should_fail_outside_their_phase() {
+        let mut server = Server::new();
+        let id = open(&mut server, 200);
+
+        for (step, body) in [
+            ("hold", r#"{"held":[]}"#),
+            ("double", r#"{"choice":"Collect"}"#),
+            ("guess", r#"{"guess":"Red"}"#),
+        ] {
+            let reply = post(&mut server, &format!("/sessions/{}/{}", id, step), body);
+            assert_eq!(
+                (reply.0, &reply.1["error"]),
+                (400, &json!("No round in progress"))
+            );
+        }
+        post(&mut server, &format!("/sessions/{}/deal", id), "");
+        let reply = post(
+            &mut server,
+            &format!("/sessions/{}/guess", id),
+            r#"{"guess":"Red"}"#,
+        );
+        assert_eq!(reply.1["error"], "Waiting for holds");
+    }
+
+    #[test]
+    fn double_should_collect_the_prize() {
+        let mut server = Server::new();
+        let id = open(&mut server, 1_000_000);
+        let reply = win(&mut server, &id);
+        let credits = session(&reply)["credits"].as_u64().unwrap();
+        let prize = session(&reply)["prize"].as_u64().unwrap();
+
+        let reply = post(
+            &mut server,
+            &format!("/sessions/{}/double", id),
+            r#"{"choice":"Collect"}"#,
+        );
+        assert_eq!(reply.0, 200);
+        assert_eq!(session(&reply)["phase"], "betting");
+        assert_eq!(session(&reply)["credits"], credits + prize);
+        let (_, history) = server.handle(&Method::Get, &format!("/sessions/{}/history", id), "");
+        assert!(!history["history"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn guess_should_settle_a_double_up_round() {
+        let mut server = Server::new();
+        let id = open(&mut server, 1_000_000);
+        win(&mut server, &id);
+
+        let reply = post(
+            &mut server,
+            &format!("/sessions/{}/double", id),
+            r#"{"choice":"Double","gamble":"Red or Black"}"#,
+        );
+        assert_eq!(session(&reply)["phase"], "guessing");
+        let reply = post(
+            &mut server,
+            &format!("/sessions/{}/guess", id),
+            r#"{"guess":"Red"}"#,
+        );
+        assert_eq!(reply.0, 200);
+        let outcome = &session(&reply)["last"]["outcome"];
+        let phase = &session(&reply)["phase"];
+        assert!(
+            (outcome == "Win" && phase == "bonus") || (outcome == "Lose" && phase == "betting")
+        );
+        let reply = post(
+            &mut server,
+            &format!("/sessions/{}/double", id),
+            r#"{"choice":"Double","gamble":"Coin toss"}"#,
+        );
+        assert_eq!(reply.0, 400);
+    }
+
+    #[test]
+    fn cashout_should_wait_for_the_round_and_close_the_session() {
+        let mut server = Server::new();
+        let id = open(&mut server, 200);
+        post(&mut server, &format!("/sessions/{}/deal", id), "");
+
+        let reply = post(&mut server, &format!("/sessions/{}/cashout", id), "");
+        assert_eq!(reply.0, 400);
+        post(
+            &mut server,
+            &format!("/sessions/{}/hold", id),
+            r#"{"held":[0,1,2,3,4]}"#,
+        );
+        post(
+            &mut server,
+            &format!("/sessions/{}/double", id),
+            r#"{"choice":"Collect"}"#,
+        );
+        let reply = post(&mut server, &format!("/sessions/{}/cashout", id), "");
+        assert_eq!(reply.0, 200);
+        assert!(reply.1["credits"].as_u64().unwrap() >= 150);
+        let reply = server.handle(&Method::Get, &format!("/sessions/{}", id), "");
+        assert_eq!(
+            (reply.0, &reply.1["error"]),
+            (404, &json!("Unknown session"))
+        );
+    }
+
+    #[test]
+    fn restart_should_keep_the_bet_of_a_round_in_progress() {
+        let data = std::env::temp_dir().join(format!("video-poker-server-{}", std::process::id()));
+        let mut server = Server::new().with_data(data.clone()).unwrap();
+        let (_, reply) = post(&mut server, "/sessions", "");
+        let id = reply["id"].as_str().unwrap().to_string();
+        post(&mut server, &format!("/sessions/{}/deal", id), "");
+
+        let mut restarted = Server::new().with_data(data.clone()).unwrap();
+        fs::remove_dir_all(&data).unwrap();
+        let reply = restarted.handle(&Method::Get, &format!("/sessions/{}", id), "");
+        assert_eq!(session(&reply)["credits"], 150);
+        assert_eq!(session(&reply)["phase"], "betting");
+    }
+
+    #[test]
+    fn unknown_routes_should_not_be_found() {
+        let mut server = Server::new();
+        let id = open(&mut server, 200);
+
+        assert_eq!(server.handle(&Method::Get, "/sessions/nope", "").0, 404);
+        assert_eq!(server.handle(&Method::Get, "/tables", "").0, 404);
+        assert_eq!(
+            post(&mut server, &format!("/sessions/{}/spin", id), "").0,
+            404
+        );
+    }
+}
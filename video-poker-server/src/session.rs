@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+use video_poker_core::bonus_game::{self, Bonus, Choice, Gamble, Guess, Hint, Outcome};
+use video_poker_core::recall::{Recall, Record};
+use video_poker_core::video_poker::{self, Hand};
+use video_poker_core::{Card, Money, score_board};
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Betting,
+    Dealt,
+    Bonus,
+    Guessing,
+}
+
+pub struct Session {
+    game: video_poker::VideoPoker,
+    bonus_game: bonus_game::BonusGame,
+    score_board: score_board::ScoreBoard,
+    recall: Recall,
+    bet: usize,
+    phase: Phase,
+    token_before: Money,
+    hand: Option<Hand>,
+    prize: Money,
+    gamble: &'static dyn Gamble,
+    face_up: Vec<Card>,
+    hints: Vec<Hint>,
+    last: Option<(Vec<Card>, Outcome)>,
+}
+
+#[derive(Serialize)]
+pub struct View<'a> {
+    credits: Money,
+    bet: usize,
+    bets: [usize; 5],
+    gambles: Vec<&'a str>,
+    phase: Phase,
+    cards: &'a [Card],
+    hand: Option<Hand>,
+    prize: Money,
+    bonus: Option<Bonus>,
+    face_up: &'a [Card],
+    hints: &'a [Hint],
+    last: Option<LastRound<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct LastRound<'a> {
+    cards: &'a [Card],
+    outcome: &'a Outcome,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Saved {
+    pub credits: Money,
+    pub bet: usize,
+    pub history: Vec<Record>,
+}
+
+impl Session {
+    const COSTS: [usize; 5] = [50, 100, 200, 500, 1000];
+    const GAMBLES: [&dyn Gamble; 4] = [
+        &bonus_game::HighLow,
+        &bonus_game::RedBlack,
+        &bonus_game::Suits,
+        &bonus_game::DealerCard,
+    ];
+    const HISTORY: usize = 100;
+
+    pub fn new(credits: Money) -> Self {
+        Session {
+            game: video_poker::VideoPoker::new(video_poker::Rule::Jokers54Cards),
+            bonus_game: bonus_game::BonusGame::new(bonus_game::Dealing::Shoe, &mut rand::rng()),
+            score_board: score_board::ScoreBoard::new(credits, score_board::Rate::JOKER_POKER)
+                .with_win_cap(Money::new(2_000_000)),
+            recall: Recall::new(Session::HISTORY),
+            bet: Session::COSTS[0],
+            phase: Phase::Betting,
+            token_before: credits,
+            hand: None,
+            prize: Money::ZERO,
+            gamble: Session::GAMBLES[0],
+            face_up: Vec::new(),
+            hints: Vec::new(),
+            last: None,
+        }
+    }
+
+    pub fn restore(saved: Saved) -> Self {
+        let mut session = Session::new(saved.credits);
+        session.bet = saved.bet;
+        saved
+            .history
+            .into_iter()
+            .rev()
+            .for_each(|record| session.recall.push(record));
+        session
+    }
+
+    // A round in progress is saved with its bet already paid, so restoring it
+    // can't take back a bad deal. Only winnings the player could still collect
+    // are saved with it.
+    pub fn save(&self) -> Saved {
+        let bonus = self.bonus_game.bonus();
+        let pending = match self.phase {
+            Phase::Betting | Phase::Dealt => Money::ZERO,
+            Phase::Bonus => self.score_board.capped(bonus.payout()),
+            Phase::Guessing => self.score_board.capped(bonus.banked),
+        };
+        Saved {
+            credits: self.score_board.token().saturating_add(pending),
+            bet: self.bet,
+            history: self.history(),
+        }
+    }
+
+    pub fn in_round(&self) -> bool {
+        self.phase != Phase::Betting
+    }
+
+    pub fn credits(&self) -> Money {
+        self.score_board.token()
+    }
+
+    pub fn history(&self) -> Vec<Record> {
+        self.recall.iter().cloned().collect()
+    }
+
+    pub fn view(&self) -> View<'_> {
+        View {
+            credits: self.score_board.token(),
+            bet: self.bet,
+            bets: Session::COSTS,
+            gambles: Session::GAMBLES
+                .iter()
+                .map(|gamble| gamble.name())
+                .collect(),
+            phase: self.phase,
+            cards: match self.phase {
+                Phase::Dealt => &self.game.round().dealt,
+                _ => self.game.cards(),
+            },
+            hand: self.hand,
+            prize: self.prize,
+            bonus: matches!(self.phase, Phase::Bonus | Phase::Guessing)
+                .then(|| self.bonus_game.bonus()),
+            face_up: &self.face_up,
+            hints: &self.hints,
+            last: self
+                .last
+                .as_ref()
+                .map(|(cards, outcome)| LastRound { cards, outcome }),
+        }
+    }
+
+    pub fn bet(&mut self, amount: usize) -> Result<(), &'static str> {
+        self.expect(Phase::Betting)?;
+        if !Session::COSTS.contains(&amount) {
+            return Err("Invalid bet");
+        }
+        self.bet = amount;
+        Ok(())
+    }
+
+    pub fn deal(&mut self) -> Result<(), &'static str> {
+        self.expect(Phase::Betting)?;
+        self.token_before = self.score_board.token();
+        self.score_board.cost(Money::new(self.bet))?;
+        self.game.deal(rand::random::<[u8; 32]>());
+        self.phase = Phase::Dealt;
+        self.hand = None;
+        self.prize = Money::ZERO;
+        self.last = None;
+        Ok(())
+    }
+
+    pub fn hold(&mut self, held: &[usize]) -> Result<(), &'static str> {
+        self.expect(Phase::Dealt)?;
+        let exchange = (0..5).filter(|i| !held.contains(i)).collect();
+        self.hand = self.game.draw(1, exchange)?.pop().flatten();
+        match self.hand {
+            Some(hand) => {
+                self.prize = self.score_board.prize_hands(
+                    &mut self.game,
+                    Money::new(self.bet),
+                    &[Some(hand)],
+                );
+                self.bonus_game.open(self.prize);
+                self.phase = Phase::Bonus;
+                Ok(())
+            }
+            None => self.finish(),
+        }
+    }
+
+    pub fn double(&mut self, choice: Choice, gamble: Option<&str>) -> Result<(), &'static str> {
+        self.expect(Phase::Bonus)?;
+        if let Some(name) = gamble {
+            self.gamble = *Session::GAMBLES
+                .iter()
+                .find(|gamble| gamble.name() == name)
+                .ok_or("Unknown gamble")?;
+        }
+        match self.bonus_game.choose(self.gamble, choice)? {
+            Some((face_up, hints)) => {
+                self.face_up = face_up;
+                self.hints = hints;
+                self.phase = Phase::Guessing;
+                Ok(())
+            }
+            None => self.finish(),
+        }
+    }
+
+    pub fn guess(&mut self, guess: Guess) -> Result<(), &'static str> {
+        self.expect(Phase::Guessing)?;
+        let (cards, outcome) = self.bonus_game.guess(self.gamble, guess)?;
+        self.face_up.clear();
+        self.hints.clear();
+        let lost = outcome == Outcome::Lose;
+        self.last = Some((cards, outcome));
+        if lost {
+            self.finish()
+        } else {
+            self.phase = Phase::Bonus;
+            Ok(())
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), &'static str> {
+        let paid = match self.hand {
+            Some(_) => self.score_board.apply(self.bonus_game.bonus()).map(|_| ()),
+            None => Ok(()),
+        };
+        self.recall.push(Record {
+            bet: Money::new(self.bet),
+            token_before: self.token_before,
+            token_after: self.score_board.token(),
+            round: self.game.round().clone(),
+            bonus: match self.hand {
+                Some(_) => self.bonus_game.rounds().to_vec(),
+                None => Vec::new(),
+            },
+        });
+        self.phase = Phase::Betting;
+        paid
+    }
+
+    fn expect(&self, phase: Phase) -> Result<(), &'static str> {
+        if self.phase == phase {
+            Ok(())
+        } else {
+            Err(match self.phase {
+                Phase::Betting => "No round in progress",
+                Phase::Dealt => "Waiting for holds",
+                Phase::Bonus => "Waiting for a double up choice",
+                Phase::Guessing => "Waiting for a guess",
+            })
+        }
+    }
+}
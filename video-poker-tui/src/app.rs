@@ -0,0 +1,153 @@
+use crate::player::TuiPlayer;
+use crate::screen::Screen;
+use ratatui::crossterm::event::KeyCode;
+use video_poker_core::bonus_game::{self, Gamble};
+use video_poker_core::video_poker::{self, Hand};
+use video_poker_core::{Money, progressive, score_board};
+
+pub struct TuiGame {
+    game: video_poker::VideoPoker,
+    bonus_game: bonus_game::BonusGame,
+    score_board: score_board::ScoreBoard,
+    bet: usize,
+    gamble: usize,
+}
+
+impl TuiGame {
+    const COSTS: [usize; 5] = [50, 100, 200, 500, 1000];
+    const GAMBLES: [&dyn Gamble; 4] = [
+        &bonus_game::HighLow,
+        &bonus_game::RedBlack,
+        &bonus_game::Suits,
+        &bonus_game::DealerCard,
+    ];
+
+    pub fn start(&mut self) {
+        let mut player = TuiPlayer::new(ratatui::init(), self.screen());
+        self.run(&mut player);
+        ratatui::restore();
+    }
+
+    fn run(&mut self, player: &mut TuiPlayer) {
+        loop {
+            self.update(player);
+            player.screen().prompt =
+                "D/Enter deal   \u{2190}/\u{2192} bet   G gamble   Q quit".to_string();
+            player.draw();
+            match player.key() {
+                KeyCode::Char('q') | KeyCode::Esc => return,
+                KeyCode::Left | KeyCode::Char('-') => self.bet = self.bet.saturating_sub(1),
+                KeyCode::Right | KeyCode::Char('+') => {
+                    self.bet = (self.bet + 1).min(TuiGame::COSTS.len() - 1)
+                }
+                KeyCode::Char('g') => self.gamble = (self.gamble + 1) % TuiGame::GAMBLES.len(),
+                KeyCode::Char('d') | KeyCode::Enter => self.play(player),
+                _ => {}
+            }
+        }
+    }
+
+    fn play(&mut self, player: &mut TuiPlayer) {
+        let bet = Money::new(TuiGame::COSTS[self.bet]);
+        if let Err(e) = self.score_board.cost(bet) {
+            player.screen().message = e.to_string();
+            return;
+        }
+        player.screen().message = String::new();
+        self.update(player);
+
+        let result = self.game.start(player, &mut rand::rng());
+        let (paid, jackpot) = match self.score_board.apply_progressive(bet, self.game.cards()) {
+            Ok(Some(jackpot)) => (true, Some(format!("JACKPOT! {} tokens", jackpot))),
+            Ok(None) => (false, None),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        let Some(hand) = result else {
+            player.screen().message = jackpot.unwrap_or_else(|| "No hand".to_string());
+            return;
+        };
+        // The progressive replaces the award of the hand, so it is never doubled.
+        if paid {
+            let mut screen = player.screen();
+            screen.hand = Some(hand);
+            screen.message = jackpot.unwrap_or_default();
+            return;
+        }
+        let prize = self
+            .score_board
+            .prize_hands(&mut self.game, bet, &[Some(hand)]);
+        let mut screen = player.screen();
+        screen.hand = Some(hand);
+        screen.message = jackpot
+            .into_iter()
+            .chain([format!("{} pays {}", hand, prize)])
+            .chain(self.game.feature_status())
+            .collect::<Vec<_>>()
+            .join("   ");
+        drop(screen);
+        self.update(player);
+
+        let bonus = self
+            .bonus_game
+            .start(TuiGame::GAMBLES[self.gamble], player, prize);
+        player.screen().message = if bonus.is_lost() && bonus.banked == Money::ZERO {
+            "You've lost the double up".to_string()
+        } else {
+            match self.score_board.apply(bonus) {
+                Ok(paid) => format!("Paid {}", paid),
+                Err(e) => e.to_string(),
+            }
+        };
+    }
+
+    fn update(&self, player: &TuiPlayer) {
+        let mut screen = player.screen();
+        screen.bet = self.bet;
+        screen.status = format!(
+            " Credits: {}   Bet: {}   Double up: {}",
+            self.score_board.token(),
+            TuiGame::COSTS[self.bet],
+            TuiGame::GAMBLES[self.gamble].name()
+        );
+        if let Some(progressive) = self.score_board.progressive() {
+            screen.status += &format!("   Jackpot: {}", progressive.amount());
+        }
+    }
+
+    fn screen(&self) -> Screen {
+        let rate = self.score_board.rate();
+        Screen {
+            paytable: Hand::ALL
+                .iter()
+                .map(|hand| (*hand, rate.by_hand(hand)))
+                .collect(),
+            bets: TuiGame::COSTS.to_vec(),
+            cards: vec![None; 5],
+            ..Screen::default()
+        }
+    }
+}
+
+impl Default for TuiGame {
+    fn default() -> Self {
+        let progressive = progressive::Progressive::new(
+            Money::new(10_000),
+            1,
+            Hand::RoyalFlush,
+            Money::new(TuiGame::COSTS[TuiGame::COSTS.len() - 1]),
+        );
+        Self {
+            game: video_poker::VideoPoker::new(video_poker::Rule::Jokers54Cards)
+                .with_feature(video_poker::SuperTimesPay::new(0.05)),
+            bonus_game: bonus_game::BonusGame::new(bonus_game::Dealing::Shoe, &mut rand::rng()),
+            score_board: score_board::ScoreBoard::new(
+                Money::new(200),
+                score_board::Rate::JOKER_POKER,
+            )
+            .with_win_cap(Money::new(2_000_000))
+            .with_progressive(progressive),
+            bet: 0,
+            gamble: 0,
+        }
+    }
+}
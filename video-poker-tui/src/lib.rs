@@ -0,0 +1,5 @@
+mod app;
+mod player;
+mod screen;
+
+pub use app::TuiGame;
@@ -0,0 +1,5 @@
+use video_poker_tui::TuiGame;
+
+fn main() {
+    TuiGame::default().start();
+}
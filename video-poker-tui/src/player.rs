@@ -0,0 +1,179 @@
+use crate::screen::{CARD_WIDTH, Screen};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use std::cell::{RefCell, RefMut};
+use std::thread;
+use std::time::Duration;
+use video_poker_core::{Card, Money, bonus_game, video_poker};
+
+const FRAME: Duration = Duration::from_millis(40);
+
+pub struct TuiPlayer {
+    terminal: RefCell<DefaultTerminal>,
+    screen: RefCell<Screen>,
+}
+
+impl TuiPlayer {
+    pub fn new(terminal: DefaultTerminal, screen: Screen) -> Self {
+        TuiPlayer {
+            terminal: RefCell::new(terminal),
+            screen: RefCell::new(screen),
+        }
+    }
+
+    pub fn screen(&self) -> RefMut<'_, Screen> {
+        self.screen.borrow_mut()
+    }
+
+    pub fn draw(&self) {
+        let screen = self.screen.borrow();
+        self.terminal
+            .borrow_mut()
+            .draw(|frame| screen.render(frame))
+            .unwrap();
+    }
+
+    pub fn key(&self) -> KeyCode {
+        loop {
+            match event::read().unwrap() {
+                Event::Key(key) if key.kind == KeyEventKind::Press => return key.code,
+                Event::Resize(_, _) => self.draw(),
+                _ => {}
+            }
+        }
+    }
+
+    fn flip(&self, i: usize, card: &Card) {
+        for width in (1..=CARD_WIDTH).rev().step_by(2) {
+            self.screen().flip = Some((i, width));
+            self.draw();
+            thread::sleep(FRAME);
+        }
+        self.screen().cards[i] = Some(card.clone());
+        for width in (1..=CARD_WIDTH).step_by(2) {
+            self.screen().flip = Some((i, width));
+            self.draw();
+            thread::sleep(FRAME);
+        }
+        self.screen().flip = None;
+    }
+}
+
+impl video_poker::Player for TuiPlayer {
+    fn show_cards(&self, cards: &[Card]) {
+        let mut screen = self.screen();
+        screen.cards = cards.iter().cloned().map(Some).collect();
+        screen.labels = vec![String::new(); cards.len()];
+        screen.hand = None;
+        drop(screen);
+        self.draw();
+    }
+
+    fn exchange(&self, cards: &[Card]) -> Vec<usize> {
+        let mut held = vec![false; cards.len()];
+        self.screen().prompt = "1-5 hold   D/Enter draw".to_string();
+        loop {
+            self.draw();
+            match self.key() {
+                KeyCode::Char(c @ '1'..='9') if (c as usize - '1' as usize) < cards.len() => {
+                    let i = c as usize - '1' as usize;
+                    held[i] = !held[i];
+                    self.screen().labels[i] = if held[i] { "HELD" } else { "" }.to_string();
+                }
+                KeyCode::Char('d') | KeyCode::Enter => {
+                    return (0..cards.len()).filter(|i| !held[*i]).collect();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl bonus_game::Player for TuiPlayer {
+    fn new_round(&self, bonus: &bonus_game::Bonus, _history: &[Card]) -> bonus_game::Choice {
+        self.screen().prompt = if bonus.banked > Money::ZERO {
+            format!(
+                "Double up {} (banked {})?   D double   H double half   C collect",
+                bonus.at_risk, bonus.banked
+            )
+        } else {
+            format!(
+                "Double up {}?   D double   H double half   C collect",
+                bonus.at_risk
+            )
+        };
+        self.draw();
+        loop {
+            match self.key() {
+                KeyCode::Char('d') => return bonus_game::Choice::Double,
+                KeyCode::Char('h') => return bonus_game::Choice::DoubleHalf,
+                KeyCode::Char('c') | KeyCode::Enter => return bonus_game::Choice::Collect,
+                _ => {}
+            }
+        }
+    }
+
+    fn guess(&self, face_up: &[Card], hints: &[bonus_game::Hint]) -> bonus_game::Guess {
+        let face_down = hints
+            .iter()
+            .filter(|hint| matches!(hint.guess, bonus_game::Guess::Pick(_)))
+            .count()
+            .max(1);
+        let mut screen = self.screen();
+        screen.cards = face_up
+            .iter()
+            .cloned()
+            .map(Some)
+            .chain((0..face_down).map(|_| None))
+            .collect();
+        screen.labels = vec![String::new(); screen.cards.len()];
+        screen.message = "Double up".to_string();
+        screen.prompt = hints
+            .iter()
+            .enumerate()
+            .map(|(i, hint)| format!("{} {} ({:.0}%)", i + 1, hint.guess, hint.win * 100.0))
+            .collect::<Vec<_>>()
+            .join("   ");
+        drop(screen);
+        self.draw();
+        loop {
+            if let KeyCode::Char(c @ '1'..='9') = self.key()
+                && let Some(hint) = hints.get(c as usize - '1' as usize)
+            {
+                return hint.guess.clone();
+            }
+        }
+    }
+
+    fn round_result(
+        &self,
+        cards: &[Card],
+        guess: &bonus_game::Guess,
+        outcome: &bonus_game::Outcome,
+    ) {
+        let face_up = self.screen().cards.iter().flatten().count();
+        let mut order: Vec<usize> = (face_up..cards.len()).collect();
+        let mut screen = self.screen();
+        screen.prompt = format!("Your guess: {}", guess);
+        if let bonus_game::Guess::Pick(i) = guess {
+            order.retain(|j| *j != face_up + i);
+            order.insert(0, face_up + i);
+            if let Some(label) = screen.labels.get_mut(face_up + i) {
+                *label = "PICK".to_string();
+            }
+        }
+        drop(screen);
+        order.into_iter().for_each(|i| self.flip(i, &cards[i]));
+        let mut screen = self.screen();
+        screen.message = match outcome {
+            bonus_game::Outcome::Win => "You've won the round",
+            bonus_game::Outcome::Lose => "You've lost",
+            bonus_game::Outcome::Push => "It's a push",
+        }
+        .to_string();
+        screen.prompt = "Press any key".to_string();
+        drop(screen);
+        self.draw();
+        self.key();
+    }
+}
@@ -0,0 +1,140 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table};
+use video_poker_core::video_poker::Hand;
+use video_poker_core::{Card, Money, Suit};
+
+pub const CARD_WIDTH: u16 = 9;
+const CARD_HEIGHT: u16 = 7;
+
+#[derive(Default)]
+pub struct Screen {
+    pub paytable: Vec<(Hand, usize)>,
+    pub bets: Vec<usize>,
+    pub bet: usize,
+    pub hand: Option<Hand>,
+    pub cards: Vec<Option<Card>>,
+    pub labels: Vec<String>,
+    pub flip: Option<(usize, u16)>,
+    pub message: String,
+    pub prompt: String,
+    pub status: String,
+}
+
+impl Screen {
+    pub fn render(&self, frame: &mut Frame) {
+        let [paytable, cards, labels, message, prompt, status] = Layout::vertical([
+            Constraint::Length(self.paytable.len() as u16 + 3),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(self.paytable(), paytable);
+        let slots = Layout::horizontal(self.cards.iter().map(|_| Constraint::Length(CARD_WIDTH)))
+            .flex(Flex::Center)
+            .spacing(2);
+        let card_slots = slots.split(cards);
+        let label_slots = slots.split(labels);
+        for (i, card) in self.cards.iter().enumerate() {
+            let width = match self.flip {
+                Some((flipping, width)) if flipping == i => width,
+                _ => CARD_WIDTH,
+            };
+            let slot = card_slots[i];
+            let area = Rect {
+                x: slot.x + (CARD_WIDTH - width) / 2,
+                width,
+                ..slot
+            };
+            frame.render_widget(Screen::card(card.as_ref()), area);
+            if let Some(label) = self.labels.get(i) {
+                frame.render_widget(
+                    Line::from(label.as_str())
+                        .centered()
+                        .style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    label_slots[i],
+                );
+            }
+        }
+        frame.render_widget(
+            Line::from(self.message.as_str())
+                .centered()
+                .style(Style::new().add_modifier(Modifier::BOLD)),
+            message,
+        );
+        frame.render_widget(Line::from(self.prompt.as_str()).centered(), prompt);
+        frame.render_widget(
+            Line::from(self.status.as_str()).style(Style::new().add_modifier(Modifier::REVERSED)),
+            status,
+        );
+    }
+
+    fn paytable(&self) -> Table<'_> {
+        let column = |i: usize| {
+            if i == self.bet {
+                Style::new().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::new()
+            }
+        };
+        let header = Row::new([Cell::from("")].into_iter().chain(
+            self.bets.iter().enumerate().map(|(i, bet)| {
+                Cell::from(Line::from(bet.to_string()).right_aligned()).style(column(i))
+            }),
+        ))
+        .style(Style::new().add_modifier(Modifier::BOLD));
+        let rows = self.paytable.iter().map(|(hand, rate)| {
+            let row = Row::new([Cell::from(hand.to_string())].into_iter().chain(
+                self.bets.iter().enumerate().map(|(i, bet)| {
+                    Cell::from(
+                        Line::from(Money::new(*bet).saturating_mul(*rate).to_string())
+                            .right_aligned(),
+                    )
+                    .style(column(i))
+                }),
+            ));
+            if self.hand == Some(*hand) {
+                row.style(Style::new().fg(Color::Green).add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        });
+        Table::new(
+            rows,
+            [Constraint::Length(16)]
+                .into_iter()
+                .chain(self.bets.iter().map(|_| Constraint::Length(8))),
+        )
+        .header(header)
+        .block(Block::bordered().title(" Paytable "))
+    }
+
+    fn card(card: Option<&Card>) -> Paragraph<'static> {
+        let Some(card) = card else {
+            return Paragraph::new(vec![Line::from("░".repeat(CARD_WIDTH as usize)); 5])
+                .style(Style::new().fg(Color::Blue))
+                .block(Block::bordered());
+        };
+        let color = match card.suit {
+            Suit::Heart | Suit::Diamond => Color::Red,
+            Suit::Joker => Color::Yellow,
+            Suit::Spade | Suit::Club => Color::White,
+        };
+        let rank = card.rank.to_string();
+        Paragraph::new(vec![
+            Line::from(rank.clone()).left_aligned(),
+            Line::from(""),
+            Line::from(card.suit.to_string()).centered(),
+            Line::from(""),
+            Line::from(rank).right_aligned(),
+        ])
+        .style(Style::new().fg(color))
+        .block(Block::bordered())
+    }
+}
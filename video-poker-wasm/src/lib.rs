@@ -0,0 +1,261 @@
+use serde::Serialize;
+use serde_wasm_bindgen::Serializer;
+use video_poker_core::Card;
+use video_poker_core::score_board::Rate;
+use video_poker_core::strategy;
+use video_poker_core::video_poker::{Hand, Rule, VideoPoker};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn evaluate(cards: JsValue) -> Result<JsValue, JsError> {
+    let cards: Vec<Card> = serde_wasm_bindgen::from_value(cards)?;
+    to_js(&Evaluation::new(&cards).map_err(JsError::new)?)
+}
+
+// The hold with the best expected return, per credit bet on the joker paytable.
+#[wasm_bindgen]
+pub fn hint(cards: JsValue, jokers: bool) -> Result<JsValue, JsError> {
+    let cards: Vec<Card> = serde_wasm_bindgen::from_value(cards)?;
+    to_js(&Hint::new(rule(jokers), &cards).map_err(JsError::new)?)
+}
+
+const HAND_SIZE: usize = 5;
+
+#[wasm_bindgen]
+pub struct Game {
+    game: VideoPoker,
+    rule: Rule,
+    seed: u64,
+    held: Vec<usize>,
+}
+
+#[wasm_bindgen]
+impl Game {
+    #[wasm_bindgen(constructor)]
+    pub fn new(jokers: bool) -> Game {
+        Game {
+            game: VideoPoker::new(rule(jokers)),
+            rule: rule(jokers),
+            seed: 0,
+            held: Vec::new(),
+        }
+    }
+
+    pub fn deal(&mut self, seed: Option<u64>) -> Result<JsValue, JsError> {
+        let seed = match seed {
+            Some(seed) => seed,
+            None => getrandom::u64().map_err(|error| JsError::new(&error.to_string()))?,
+        };
+        to_js(&self.deal_seeded(seed).map_err(JsError::new)?)
+    }
+
+    pub fn hold(&mut self, index: usize) -> Result<JsValue, JsError> {
+        to_js(&self.toggle(index).map_err(JsError::new)?)
+    }
+
+    pub fn draw(&mut self) -> Result<JsValue, JsError> {
+        to_js(&self.draw_held().map_err(JsError::new)?)
+    }
+
+    pub fn hint(&self) -> Result<JsValue, JsError> {
+        to_js(&self.best_hold().map_err(JsError::new)?)
+    }
+}
+
+impl Game {
+    fn deal_seeded(&mut self, seed: u64) -> Result<Round<'_>, &'static str> {
+        self.seed = seed;
+        self.held.clear();
+        let cards = self.game.deal(seed);
+        Ok(Round {
+            seed,
+            cards,
+            held: positions(&self.held),
+            evaluation: Evaluation::new(cards)?,
+        })
+    }
+
+    fn toggle(&mut self, index: usize) -> Result<Vec<u32>, &'static str> {
+        if !self.game.is_dealt() {
+            return Err("No cards dealt");
+        }
+        if index >= self.game.round().dealt.len() {
+            return Err("Invalid position");
+        }
+        match self.held.iter().position(|i| *i == index) {
+            Some(position) => {
+                self.held.remove(position);
+            }
+            None => self.held.push(index),
+        }
+        self.held.sort();
+        Ok(positions(&self.held))
+    }
+
+    fn best_hold(&self) -> Result<Hint, &'static str> {
+        if !self.game.is_dealt() {
+            return Err("No cards dealt");
+        }
+        Hint::new(self.rule, &self.game.round().dealt)
+    }
+
+    fn draw_held(&mut self) -> Result<Round<'_>, &'static str> {
+        let exchange = (0..self.game.round().dealt.len())
+            .filter(|i| !self.held.contains(i))
+            .collect();
+        self.game.draw(1, exchange)?;
+        let round = self.game.round();
+        Ok(Round {
+            seed: self.seed,
+            cards: self.game.cards(),
+            held: positions(&round.held),
+            evaluation: Evaluation::new(self.game.cards())?,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Evaluation {
+    hand: Option<Hand>,
+    label: Option<String>,
+}
+
+impl Evaluation {
+    fn new(cards: &[Card]) -> Result<Self, &'static str> {
+        if cards.len() != HAND_SIZE {
+            return Err("A hand has five cards");
+        }
+        let hand = Hand::from_cards(cards);
+        Ok(Evaluation {
+            hand,
+            label: hand.map(|hand| hand.to_string()),
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Hint {
+    held: Vec<u32>,
+    ev: f64,
+}
+
+impl Hint {
+    fn new(rule: Rule, cards: &[Card]) -> Result<Self, &'static str> {
+        if cards.len() != HAND_SIZE {
+            return Err("A hand has five cards");
+        }
+        let (held, ev) = strategy::best_hold(rule, &Rate::JOKER_POKER, cards);
+        Ok(Hint {
+            held: positions(&held),
+            ev,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Round<'a> {
+    seed: u64,
+    cards: &'a [Card],
+    held: Vec<u32>,
+    #[serde(flatten)]
+    evaluation: Evaluation,
+}
+
+fn rule(jokers: bool) -> Rule {
+    if jokers {
+        Rule::Jokers54Cards
+    } else {
+        Rule::Default52Cards
+    }
+}
+
+fn positions(held: &[usize]) -> Vec<u32> {
+    held.iter().map(|i| *i as u32).collect()
+}
+
+fn to_js(value: &impl Serialize) -> Result<JsValue, JsError> {
+    Ok(value.serialize(
+        &Serializer::new()
+            .serialize_missing_as_null(true)
+            .serialize_maps_as_objects(true)
+            .serialize_large_number_types_as_bigints(true),
+    )?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cards(cards: &str) -> Vec<Card> {
+        cards.split(' ').map(|card| card.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn evaluation_should_reject_anything_but_five_cards() {
+        assert!(Evaluation::new(&[]).is_err());
+        assert!(Evaluation::new(&cards("♥A ♥K ♥Q ♥J")).is_err());
+        assert!(Evaluation::new(&cards("♥A ♥K ♥Q ♥J ♥10 ♥9")).is_err());
+    }
+
+    #[test]
+    fn evaluation_should_label_the_hand() {
+        let evaluation = Evaluation::new(&cards("♥A ♥K ♥Q ♥J ♥10")).unwrap();
+        assert_eq!(evaluation.hand, Some(Hand::RoyalFlush));
+        assert_eq!(evaluation.label, Some(Hand::RoyalFlush.to_string()));
+        let evaluation = Evaluation::new(&cards("♥2 ♠5 ♦7 ♣9 ♥J")).unwrap();
+        assert_eq!(evaluation.hand, None);
+        assert_eq!(evaluation.label, None);
+    }
+
+    #[test]
+    fn deal_should_repeat_with_the_same_seed() {
+        let mut game = Game::new(false);
+        let first = game.deal_seeded(42).unwrap().cards.to_vec();
+        let mut other = Game::new(false);
+        assert_eq!(other.deal_seeded(42).unwrap().cards, first.as_slice());
+    }
+
+    #[test]
+    fn hold_should_toggle_the_positions() {
+        let mut game = Game::new(false);
+        assert_eq!(game.toggle(0), Err("No cards dealt"));
+        game.deal_seeded(1).unwrap();
+        assert_eq!(game.toggle(3), Ok(vec![3]));
+        assert_eq!(game.toggle(1), Ok(vec![1, 3]));
+        assert_eq!(game.toggle(3), Ok(vec![1]));
+        assert_eq!(game.toggle(5), Err("Invalid position"));
+    }
+
+    #[test]
+    fn draw_should_keep_the_held_cards() {
+        let mut game = Game::new(true);
+        let dealt = game.deal_seeded(7).unwrap().cards.to_vec();
+        game.toggle(0).unwrap();
+        game.toggle(4).unwrap();
+        let round = game.draw_held().unwrap();
+        assert_eq!(round.held, vec![0, 4]);
+        assert_eq!(round.cards[0], dealt[0]);
+        assert_eq!(round.cards[4], dealt[4]);
+        assert!(game.draw_held().is_err());
+    }
+
+    #[test]
+    fn hint_should_hold_a_dealt_royal_flush() {
+        let hint = Hint::new(Rule::Default52Cards, &cards("♥10 ♥J ♥Q ♥K ♥A")).unwrap();
+
+        assert_eq!(hint.held, vec![0, 1, 2, 3, 4]);
+        assert_eq!(hint.ev, Rate::JOKER_POKER.royal_flush as f64);
+        assert!(Hint::new(Rule::Default52Cards, &cards("♥10 ♥J ♥Q ♥K")).is_err());
+    }
+
+    #[test]
+    fn hint_should_need_a_deal() {
+        let mut game = Game::new(false);
+        assert!(game.best_hold().is_err());
+        game.deal_seeded(3).unwrap();
+        let hint = game.best_hold().unwrap();
+
+        assert!(hint.held.iter().all(|i| *i < 5));
+        assert!(hint.ev >= 0.0);
+    }
+}